@@ -1,5 +1,6 @@
 use chacha20::u96;
 use io_utils::ReadExt;
+use poly1305::{MacError, Poly1305};
 use std::io::{self, Read, Write};
 
 struct Pad16<R: Read> {
@@ -119,15 +120,21 @@ pub fn compute_tag(
     poly1305::poly1305(mac_data, &otk)
 }
 
+/// Checks `tag` against the additional data and the ciphertext, in constant time.
+///
+/// The outer result holds the IO errors, the inner one whether the tag matches.
 pub fn check_tag(
     key: &[u8; 32],
     nonce: u96,
     aad: impl Read,
     cipher: impl Read,
-) -> io::Result<u128> {
+    tag: &[u8; 16],
+) -> io::Result<Result<(), MacError>> {
     let otk: [u8; 32] = chacha20::block(key, 0, nonce)[..32].try_into().unwrap();
 
-    let mac_data = ConcatLen::new(aad, cipher);
+    let mut mac_data = ConcatLen::new(aad, cipher);
+    let mut mac = Poly1305::new(&otk);
+    io::copy(&mut mac_data, &mut mac)?;
 
-    poly1305::poly1305(mac_data, &otk)
+    Ok(mac.verify(tag))
 }
@@ -7,12 +7,6 @@ use std::{
     process::ExitCode,
 };
 
-fn parse_tag(data: &str) -> Result<u128, String> {
-    io_utils::parse_hex(data)
-        .map(u128::from_le_bytes)
-        .ok_or_else(|| "Invalid tag, must be a 16 bytes hex number".to_string())
-}
-
 /// Unwrap data in the ChaCha/Poly AEAD scheme. Outputs the plaintext.
 #[derive(FromArgs)]
 struct Opts {
@@ -29,8 +23,8 @@ struct Opts {
     #[argh(positional)]
     ciphertext: String,
     /// poly1305 tag
-    #[argh(positional, from_str_fn(parse_tag))]
-    tag: u128,
+    #[argh(positional, from_str_fn(poly1305::parse_tag))]
+    tag: [u8; 16],
     /// plaintext output location, empty for stdout
     #[argh(positional)]
     plaintext: Option<PathBuf>,
@@ -54,10 +48,16 @@ fn main() -> ExitCode {
 
     let mut ciphertext = File::open(&opts.ciphertext).expect("Could not open plaintext file");
 
-    let tag = aead_chacha_poly::check_tag(&key, opts.nonce, aad, BufReader::new(&mut ciphertext))
-        .expect("IO error");
+    let checked = aead_chacha_poly::check_tag(
+        &key,
+        opts.nonce,
+        aad,
+        BufReader::new(&mut ciphertext),
+        &opts.tag,
+    )
+    .expect("IO error");
 
-    if tag != opts.tag {
+    if checked.is_err() {
         return ExitCode::FAILURE;
     }
 
@@ -29,11 +29,13 @@ fn wrap() {
 fn unwrap() {
     let mut output = Vec::with_capacity(SUNSCREEN.len());
 
-    let tag = check_tag(KEYFILE, NONCE, ADFILE, CIPHERTEXT).unwrap();
+    let checked = check_tag(KEYFILE, NONCE, ADFILE, CIPHERTEXT, &TAG.to_le_bytes()).unwrap();
+    let wrong = check_tag(KEYFILE, NONCE, ADFILE, CIPHERTEXT, &(TAG ^ 1).to_le_bytes()).unwrap();
 
     let mut decipher = chacha20::cipher(KEYFILE, NONCE, CIPHERTEXT);
     decipher.read_to_end(&mut output).unwrap();
 
-    assert_eq!(tag, TAG);
+    assert!(checked.is_ok());
+    assert!(wrong.is_err());
     assert_eq!(output.as_slice(), SUNSCREEN);
 }
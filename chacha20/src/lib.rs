@@ -1,5 +1,5 @@
 use io_utils::ReadExt;
-use std::io::{self, Read};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 
 #[derive(Copy, Clone, Debug)]
 struct State([u32; 16]);
@@ -15,6 +15,12 @@ pub type u96 = (u32, u32, u32);
 
 impl State {
     fn new(key: &[u8; 32], b: u32, n: u96) -> Self {
+        Self::with_input(key, [b, n.0, n.1, n.2])
+    }
+
+    // the last four words are the counter and nonce for ChaCha20,
+    // or the 16-byte nonce for HChaCha20
+    fn with_input(key: &[u8; 32], input: [u32; 4]) -> Self {
         let mut res = [0; 16];
         res[0] = C0;
         res[1] = C1;
@@ -23,10 +29,7 @@ impl State {
 
         bytemuck::must_cast_slice_mut(&mut res[4..12]).copy_from_slice(key.as_slice());
 
-        res[12] = b;
-        res[13] = n.0;
-        res[14] = n.1;
-        res[15] = n.2;
+        res[12..].copy_from_slice(&input);
 
         Self(res)
     }
@@ -71,11 +74,15 @@ impl State {
         self.quarter_round(3, 4, 9, 14);
     }
 
-    fn block_round(&mut self) {
-        let init = self.0;
-        for _ in 0..10 {
+    fn rounds<const ROUNDS: usize>(&mut self) {
+        for _ in 0..ROUNDS / 2 {
             self.double_round();
         }
+    }
+
+    fn block_round<const ROUNDS: usize>(&mut self) {
+        let init = self.0;
+        self.rounds::<ROUNDS>();
         self.0
             .iter_mut()
             .zip(init)
@@ -88,50 +95,367 @@ impl State {
 }
 
 pub fn block(key: &[u8; 32], counter: u32, nonce: u96) -> [u8; 64] {
+    block_with_rounds::<20>(key, counter, nonce)
+}
+
+/// A block of ChaCha with `ROUNDS` rounds, which must be even.
+pub fn block_with_rounds<const ROUNDS: usize>(
+    key: &[u8; 32],
+    counter: u32,
+    nonce: u96,
+) -> [u8; 64] {
+    const {
+        assert!(
+            ROUNDS.is_multiple_of(2),
+            "ChaCha has an even number of rounds"
+        )
+    };
+
     let mut state = State::new(key, counter, nonce);
-    state.block_round();
+    state.block_round::<ROUNDS>();
     *state.serialize()
 }
 
-pub struct Cipher<R: Read> {
+/// HChaCha20: derives a subkey from `key` and the first 16 bytes of an XChaCha20 nonce.
+pub fn hchacha20(key: &[u8; 32], nonce: &[u8; 16]) -> [u8; 32] {
+    let nonce: [u32; 4] = bytemuck::must_cast(*nonce);
+    let mut state = State::with_input(key, nonce.map(u32::from_le));
+    state.rounds::<20>();
+
+    // first and last rows, without the final addition
+    let mut subkey = [0; 8];
+    subkey[..4].copy_from_slice(&state.0[..4]);
+    subkey[4..].copy_from_slice(&state.0[12..]);
+    bytemuck::must_cast(subkey.map(u32::to_le))
+}
+
+/// Layout of the block counter and nonce in the last four words of the state.
+pub trait Variant {
+    type Nonce: Copy;
+
+    /// Largest block counter, after which the keystream is exhausted.
+    const MAX_COUNTER: u64;
+
+    fn input(counter: u64, nonce: Self::Nonce) -> [u32; 4];
+}
+
+/// The IETF variant of RFC 8439: a 32-bit counter and a 96-bit nonce,
+/// for at most 256 GiB of keystream per nonce.
+#[derive(Clone, Copy, Debug)]
+pub struct Ietf;
+
+impl Variant for Ietf {
+    type Nonce = u96;
+
+    const MAX_COUNTER: u64 = u32::MAX as u64;
+
+    fn input(counter: u64, nonce: u96) -> [u32; 4] {
+        [counter as u32, nonce.0, nonce.1, nonce.2]
+    }
+}
+
+/// The original variant of D. J. Bernstein: a 64-bit counter and a 64-bit nonce,
+/// as used by libsodium's `crypto_stream_chacha20` and OpenSSH.
+#[derive(Clone, Copy, Debug)]
+pub struct Djb;
+
+impl Variant for Djb {
+    type Nonce = u64;
+
+    const MAX_COUNTER: u64 = u64::MAX;
+
+    fn input(counter: u64, nonce: u64) -> [u32; 4] {
+        [
+            counter as u32,
+            (counter >> 32) as u32,
+            nonce as u32,
+            (nonce >> 32) as u32,
+        ]
+    }
+}
+
+/// A ChaCha keystream with `ROUNDS` rounds, applied to slices in place.
+///
+/// The position in the keystream is kept between calls, so that a message
+/// can be encrypted in pieces of any length.
+#[derive(Clone)]
+pub struct Keystream<V: Variant = Ietf, const ROUNDS: usize = 20> {
     key: [u8; 32],
-    nonce: u96,
+    nonce: V::Nonce,
+
+    // counter of the next block, if any
+    counter: Option<u64>,
+    block: [u8; 64],
+    pos: usize,
+}
+
+impl<V: Variant, const ROUNDS: usize> Keystream<V, ROUNDS> {
+    /// Keystream starting at block counter `counter`.
+    pub fn new(key: &[u8; 32], nonce: V::Nonce, counter: u64) -> Self {
+        const {
+            assert!(
+                ROUNDS.is_multiple_of(2),
+                "ChaCha has an even number of rounds"
+            )
+        };
+
+        let mut keystream = Self {
+            key: *key,
+            nonce,
+            counter: None,
+            block: [0; 64],
+            pos: 64,
+        };
+        keystream.set_counter(Some(counter));
+        keystream
+    }
+
+    // moves to the start of the block `counter`, or to the end of the keystream
+    fn set_counter(&mut self, counter: Option<u64>) {
+        self.counter = counter.filter(|&c| c <= V::MAX_COUNTER);
+        self.pos = 64;
+    }
+
+    // number of keystream bytes left, saturated at u64::MAX
+    fn remaining(&self) -> u64 {
+        let blocks = self
+            .counter
+            .map_or(0, |c| (V::MAX_COUNTER - c).saturating_add(1));
+        blocks
+            .saturating_mul(64)
+            .saturating_add(64 - self.pos as u64)
+    }
+
+    /// XORs `data` with the next bytes of the keystream.
+    ///
+    /// Fails without changing `data` when the keystream is too short for it.
+    pub fn apply_keystream(&mut self, data: &mut [u8]) -> io::Result<()> {
+        if data.len() as u64 > self.remaining() {
+            return Err(io::Error::other("ChaCha keystream exhausted"));
+        }
+
+        let mut data = data;
+        while !data.is_empty() {
+            if self.pos == 64 {
+                // checked above
+                let counter = self.counter.unwrap();
+                self.counter = counter.checked_add(1).filter(|&c| c <= V::MAX_COUNTER);
+
+                let mut state = State::with_input(&self.key, V::input(counter, self.nonce));
+                state.block_round::<ROUNDS>();
+                self.block = *state.serialize();
+                self.pos = 0;
+            }
+
+            let n = data.len().min(64 - self.pos);
+            let (head, rest) = data.split_at_mut(n);
+            head.iter_mut()
+                .zip(&self.block[self.pos..])
+                .for_each(|(d, k)| *d ^= k);
+
+            self.pos += n;
+            data = rest;
+        }
+
+        Ok(())
+    }
+}
+
+/// ChaCha encryption of a reader, with `ROUNDS` rounds.
+pub struct Cipher<R: Read, V: Variant = Ietf, const ROUNDS: usize = 20> {
     reader: R,
+    keystream: Keystream<V, ROUNDS>,
 
-    counter: u32,
+    // counter of the first block
+    initial: u64,
+    // position in the source of the next byte read
+    position: u64,
     block: [u8; 64],
     pos: usize,
 }
 
-impl<R: Read> Read for Cipher<R> {
+pub type ChaCha8<R, V = Ietf> = Cipher<R, V, 8>;
+pub type ChaCha12<R, V = Ietf> = Cipher<R, V, 12>;
+pub type ChaCha20<R, V = Ietf> = Cipher<R, V, 20>;
+
+impl<R: Read, V: Variant, const ROUNDS: usize> Cipher<R, V, ROUNDS> {
+    /// Encryption of `input`, starting at block counter `counter`.
+    pub fn new(key: &[u8; 32], nonce: V::Nonce, counter: u64, input: R) -> Self {
+        Self {
+            reader: input,
+            keystream: Keystream::new(key, nonce, counter),
+            block: [0; 64],
+            pos: 64,
+            initial: counter,
+            position: 0,
+        }
+    }
+
+    // reads and encrypts the next block of input, stored at the end of `block`
+    fn next_block(&mut self) -> io::Result<()> {
+        let n = self.reader.read_all(self.block.as_mut_slice())?;
+        self.pos = 64 - n;
+
+        self.block.copy_within(..n, self.pos);
+        let result = self.keystream.apply_keystream(&mut self.block[self.pos..]);
+        if result.is_err() {
+            // the input that could not be encrypted must not be read later
+            self.block = [0; 64];
+            self.pos = 64;
+        }
+        result
+    }
+}
+
+impl<R: Read, V: Variant, const ROUNDS: usize> Read for Cipher<R, V, ROUNDS> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         if self.pos >= 64 {
-            self.counter += 1;
-            let n = self.reader.read_all(self.block.as_mut_slice())?;
-            self.pos = 64 - n;
-
-            self.block.copy_within(..n, self.pos);
-            let data = block(&self.key, self.counter, self.nonce);
-            self.block[self.pos..]
-                .iter_mut()
-                .zip(data)
-                .for_each(|(d, c)| *d ^= c);
+            self.next_block()?;
         }
         let n = (&self.block[self.pos..]).read(buf)?;
         self.pos += n;
+        self.position += n as u64;
         Ok(n)
     }
 }
 
-pub fn cipher<R: Read>(key: &[u8; 32], nonce: u96, input: R) -> Cipher<R> {
-    Cipher {
-        reader: input,
-        key: *key,
-        nonce,
-        block: [0; 64],
-        pos: 64,
-        counter: 0,
+/// Seeking in the cipher seeks in the source, and in the keystream.
+///
+/// Positions are those of the source: its byte at position n is encrypted with
+/// the byte n of the keystream, so the source should start with the encrypted data.
+impl<R: Read + Seek, V: Variant, const ROUNDS: usize> Seek for Cipher<R, V, ROUNDS> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::End(_) => self.reader.seek(pos)?,
+            SeekFrom::Current(offset) => {
+                self.position.checked_add_signed(offset).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "invalid seek before start")
+                })?
+            }
+        };
+
+        let counter = self
+            .initial
+            .checked_add(target / 64)
+            .filter(|&c| c <= V::MAX_COUNTER);
+        let beyond = || io::Error::new(io::ErrorKind::InvalidInput, "seek beyond the keystream");
+
+        // only the source knows where its end is, so it has already moved there
+        if !matches!(pos, SeekFrom::End(_)) {
+            counter.ok_or_else(beyond)?;
+            self.reader.seek(SeekFrom::Start(target))?;
+        }
+
+        // the source is at the target: drop the block read ahead, and skip
+        // the keystream of the bytes before the target in its block
+        self.position = target;
+        self.pos = 64;
+        self.keystream.set_counter(counter);
+        counter.ok_or_else(beyond)?;
+        self.keystream
+            .apply_keystream(&mut [0; 64][..(target % 64) as usize])?;
+
+        Ok(target)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.position)
+    }
+}
+
+/// ChaCha encryption of everything written to it, with `ROUNDS` rounds,
+/// towards a writer.
+pub struct CipherWriter<W: Write, V: Variant = Ietf, const ROUNDS: usize = 20> {
+    writer: W,
+    keystream: Keystream<V, ROUNDS>,
+}
+
+impl<W: Write, V: Variant, const ROUNDS: usize> CipherWriter<W, V, ROUNDS> {
+    /// Encryption towards `output`, starting at block counter `counter`.
+    pub fn new(key: &[u8; 32], nonce: V::Nonce, counter: u64, output: W) -> Self {
+        Self {
+            writer: output,
+            keystream: Keystream::new(key, nonce, counter),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write, V: Variant, const ROUNDS: usize> Write for CipherWriter<W, V, ROUNDS> {
+    /// Data is only accepted once it has all been written to the inner writer:
+    /// if that fails, the keystream and the output are out of step.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut block = [0; 1 << 12];
+        let n = buf.len().min(block.len());
+
+        let block = &mut block[..n];
+        block.copy_from_slice(&buf[..n]);
+        self.keystream.apply_keystream(block)?;
+        self.writer.write_all(block)?;
+
+        Ok(n)
     }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// ChaCha20 encryption of `input`, starting at block counter 1 as in RFC 8439.
+pub fn cipher<R: Read>(key: &[u8; 32], nonce: u96, input: R) -> Cipher<R> {
+    cipher_with_counter(key, nonce, 1, input)
+}
+
+/// ChaCha20 encryption of `input`, starting at block counter `counter`.
+pub fn cipher_with_counter<R: Read>(
+    key: &[u8; 32],
+    nonce: u96,
+    counter: u32,
+    input: R,
+) -> Cipher<R> {
+    Cipher::new(key, nonce, counter.into(), input)
+}
+
+/// ChaCha20 keystream, starting at block counter 1 as in RFC 8439.
+pub fn keystream(key: &[u8; 32], nonce: u96) -> Keystream {
+    Keystream::new(key, nonce, 1)
+}
+
+/// ChaCha20 encryption towards `output`, starting at block counter 1 as in RFC 8439.
+pub fn cipher_writer<W: Write>(key: &[u8; 32], nonce: u96, output: W) -> CipherWriter<W> {
+    CipherWriter::new(key, nonce, 1, output)
+}
+
+/// ChaCha20 encryption of `input` with the original 64-bit nonce, starting at block counter 0.
+pub fn cipher_djb<R: Read>(key: &[u8; 32], nonce: u64, input: R) -> Cipher<R, Djb> {
+    Cipher::new(key, nonce, 0, input)
+}
+
+/// XChaCha20: ChaCha20 with a 24-byte nonce, long enough to be chosen at random.
+///
+/// The key is replaced by the HChaCha20 subkey of the first 16 bytes of the nonce,
+/// and the last 8 bytes, prefixed with 4 zero bytes, are the ChaCha20 nonce.
+/// The keystream starts at block counter 1, as in XChaCha20-Poly1305 where
+/// block 0 is the one-time Poly1305 key: see [`xcipher_with_counter`] for others.
+pub fn xcipher<R: Read>(key: &[u8; 32], nonce: &[u8; 24], input: R) -> Cipher<R> {
+    xcipher_with_counter(key, nonce, 1, input)
+}
+
+/// XChaCha20 encryption of `input`, starting at block counter `counter`.
+pub fn xcipher_with_counter<R: Read>(
+    key: &[u8; 32],
+    nonce: &[u8; 24],
+    counter: u32,
+    input: R,
+) -> Cipher<R> {
+    let subkey = hchacha20(key, nonce[..16].try_into().unwrap());
+    let word = |i: usize| u32::from_le_bytes(nonce[i..i + 4].try_into().unwrap());
+
+    cipher_with_counter(&subkey, (0, word(16), word(20)), counter, input)
 }
 
 pub fn parse_nonce(data: &str) -> Result<u96, String> {
@@ -144,6 +468,11 @@ pub fn parse_nonce(data: &str) -> Result<u96, String> {
     Ok((data[0], data[1], data[2]))
 }
 
+pub fn parse_xnonce(data: &str) -> Result<[u8; 24], String> {
+    io_utils::parse_hex::<24>(data)
+        .ok_or_else(|| "Invalid nonce: must be a 24 bytes hex number".to_string())
+}
+
 #[cfg(test)]
 #[test]
 fn chacha20() {
@@ -170,7 +499,7 @@ fn chacha20() {
     ];
 
     let mut state = State::new(&key, b, n);
-    state.block_round();
+    state.block_round::<20>();
 
     assert_eq!(state.serialize(), &end_block);
 }
@@ -1,15 +1,8 @@
 use std::io::Read;
 
-use chacha20::u96;
-
-const SUNSCREEN: &[u8] = include_bytes!("sunscreen.txt");
-const KEYFILE: &[u8; 32] = include_bytes!("keyfile");
-const NONCE: u96 = (
-    u32::from_le_bytes([0x00, 0x00, 0x00, 0x00]),
-    u32::from_le_bytes([0x00, 0x00, 0x00, 0x4a]),
-    u32::from_le_bytes([0x00, 0x00, 0x00, 0x00]),
-);
-const CIPHERTEXT: &[u8] = include_bytes!("ciphertext.bin");
+mod common;
+
+use common::{CIPHERTEXT, KEYFILE, NONCE, SUNSCREEN};
 
 #[test]
 fn cipher() {
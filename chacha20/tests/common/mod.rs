@@ -0,0 +1,12 @@
+//! RFC 8439 section 2.4.2 sample, shared by the test files
+
+use chacha20::u96;
+
+pub const SUNSCREEN: &[u8] = include_bytes!("../sunscreen.txt");
+pub const KEYFILE: &[u8; 32] = include_bytes!("../keyfile");
+pub const NONCE: u96 = (
+    u32::from_le_bytes([0x00, 0x00, 0x00, 0x00]),
+    u32::from_le_bytes([0x00, 0x00, 0x00, 0x4a]),
+    u32::from_le_bytes([0x00, 0x00, 0x00, 0x00]),
+);
+pub const CIPHERTEXT: &[u8] = include_bytes!("../ciphertext.bin");
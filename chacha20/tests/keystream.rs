@@ -0,0 +1,54 @@
+use std::io::{self, Write};
+
+mod common;
+
+use common::{CIPHERTEXT, KEYFILE, NONCE, SUNSCREEN};
+
+#[test]
+fn apply_keystream() {
+    for piece in [1, 7, 63, 64, 65, 200] {
+        let mut data = SUNSCREEN.to_vec();
+        let mut keystream = chacha20::keystream(KEYFILE, NONCE);
+        for chunk in data.chunks_mut(piece) {
+            keystream.apply_keystream(chunk).unwrap();
+        }
+        assert_eq!(data, CIPHERTEXT, "pieces of {piece} bytes");
+
+        // and back
+        let mut keystream = chacha20::keystream(KEYFILE, NONCE);
+        keystream.apply_keystream(&mut data).unwrap();
+        assert_eq!(data, SUNSCREEN);
+    }
+}
+
+#[test]
+fn cipher_writer() {
+    let mut writer = chacha20::cipher_writer(KEYFILE, NONCE, Vec::new());
+    for chunk in SUNSCREEN.chunks(10) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.flush().unwrap();
+    assert_eq!(writer.into_inner(), CIPHERTEXT);
+
+    let mut writer = chacha20::cipher_writer(KEYFILE, NONCE, Vec::new());
+    io::copy(&mut &CIPHERTEXT[..], &mut writer).unwrap();
+    assert_eq!(writer.into_inner(), SUNSCREEN);
+}
+
+#[test]
+fn exhaustion() {
+    let mut keystream = chacha20::Keystream::<chacha20::Ietf>::new(KEYFILE, NONCE, u32::MAX.into());
+
+    // too long: nothing is changed
+    let mut data = [0; 65];
+    assert!(keystream.apply_keystream(&mut data).is_err());
+    assert_eq!(data, [0; 65]);
+
+    keystream.apply_keystream(&mut data[..60]).unwrap();
+    keystream.apply_keystream(&mut data[60..64]).unwrap();
+    assert_eq!(data[..64], chacha20::block(KEYFILE, u32::MAX, NONCE));
+
+    let err = keystream.apply_keystream(&mut data[64..]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    keystream.apply_keystream(&mut []).unwrap();
+}
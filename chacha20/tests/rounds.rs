@@ -0,0 +1,90 @@
+use std::io::{self, Read};
+
+use chacha20::{ChaCha12, ChaCha20, ChaCha8, Djb};
+use io_utils::hex;
+
+fn keystream<R: Read>(mut cipher: R) -> Vec<u8> {
+    let mut keystream = Vec::new();
+    cipher.read_to_end(&mut keystream).unwrap();
+    keystream
+}
+
+fn zeros(len: u64) -> io::Take<io::Repeat> {
+    io::repeat(0).take(len)
+}
+
+// test case 1 of draft-strombergson-chacha-test-vectors: all-zero key and nonce
+#[test]
+fn zero_key() {
+    let chacha8 = hex!(
+        "3e00ef2f895f40d67f5bb8e81f09a5a12c840ec3ce9a7f3b181be188ef711a1e\
+         984ce172b9216f419f445367456d5619314a42a3da86b001387bfdb80e0cfe42"
+    );
+    let chacha12 = hex!(
+        "9bf49a6a0755f953811fce125f2683d50429c3bb49e074147e0089a52eae155f\
+         0564f879d27ae3c02ce82834acfa8c793a629f2ca0de6919610be82f411326be"
+    );
+    let chacha20 = hex!(
+        "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7\
+         da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586"
+    );
+
+    let key = [0; 32];
+    assert_eq!(
+        keystream(ChaCha8::<_, Djb>::new(&key, 0, 0, zeros(64))),
+        chacha8
+    );
+    assert_eq!(
+        keystream(ChaCha12::<_, Djb>::new(&key, 0, 0, zeros(64))),
+        chacha12
+    );
+    assert_eq!(
+        keystream(ChaCha20::<_, Djb>::new(&key, 0, 0, zeros(64))),
+        chacha20
+    );
+
+    assert_eq!(
+        chacha20::block_with_rounds::<8>(&key, 0, (0, 0, 0)),
+        chacha8
+    );
+    assert_eq!(
+        chacha20::block_with_rounds::<12>(&key, 0, (0, 0, 0)),
+        chacha12
+    );
+    assert_eq!(chacha20::block(&key, 0, (0, 0, 0)), chacha20);
+}
+
+// computed with a Python implementation of the block function
+#[test]
+fn ietf_layout() {
+    let key = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+    let nonce = (0, 0x4a000000, 0);
+
+    let chacha8 = hex!(
+        "bc08fed3f82c571c5e7a70866588aee281ee18680869a9c2af9f4e244a4a5637\
+         61b2dfe8a747dafd532f8496553311589abd3ec1eb4576054477a7295b82cbb7\
+         2872607d86b93d80e3e7fea72806341fa1118239138e2e78d2a997d40f51647f\
+         cb7729a690cf215ad44474ab0d6c09bb8adc497ebd6d34010937d25eead815da"
+    );
+    let chacha12 = hex!(
+        "c126863f9577559308796ff81a44655bd352630c35bd4beccbad4b6fdd7b608f\
+         8ba8301c3a1e8f0643571dbe21583d5f622a60f4321e1243b88a4796306f9122\
+         116dfc0ae8f83bc3ac1b0dac1966a79f1cc09fada6bfb33bfaaa0a9101e5f7a4\
+         54c689ced448424d3d53935e00d37e50be76c1b4d844c79e2574114bb9d34270"
+    );
+
+    assert_eq!(
+        keystream(ChaCha8::<_>::new(&key, nonce, 1, zeros(128))),
+        chacha8
+    );
+    assert_eq!(
+        keystream(ChaCha12::<_>::new(&key, nonce, 1, zeros(128))),
+        chacha12
+    );
+
+    // ChaCha20 is what `cipher` computes
+    assert_eq!(
+        keystream(ChaCha20::<_>::new(&key, nonce, 1, zeros(128))),
+        keystream(chacha20::cipher(&key, nonce, zeros(128)))
+    );
+}
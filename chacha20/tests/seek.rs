@@ -0,0 +1,92 @@
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+mod common;
+
+use common::{CIPHERTEXT, KEYFILE, NONCE, SUNSCREEN};
+
+fn read_rest(reader: &mut impl Read) -> Vec<u8> {
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    rest
+}
+
+#[test]
+fn seek_start() {
+    let mut cipher = chacha20::cipher(KEYFILE, NONCE, Cursor::new(SUNSCREEN));
+
+    // forwards and backwards, inside and across blocks
+    for offset in [0, 1, 63, 64, 65, 100, 13, 113, 114, 200] {
+        let pos = cipher.seek(SeekFrom::Start(offset)).unwrap();
+        assert_eq!(pos, offset);
+
+        let expected = CIPHERTEXT.get(offset as usize..).unwrap_or_default();
+        assert_eq!(read_rest(&mut cipher), expected, "offset {offset}");
+    }
+}
+
+#[test]
+fn seek_relative() {
+    let mut cipher = chacha20::cipher(KEYFILE, NONCE, Cursor::new(CIPHERTEXT));
+
+    let mut start = [0; 10];
+    cipher.read_exact(&mut start).unwrap();
+    assert_eq!(start, SUNSCREEN[..10]);
+    assert_eq!(cipher.stream_position().unwrap(), 10);
+
+    assert_eq!(cipher.seek(SeekFrom::Current(60)).unwrap(), 70);
+    let mut middle = [0; 20];
+    cipher.read_exact(&mut middle).unwrap();
+    assert_eq!(middle, SUNSCREEN[70..90]);
+
+    assert_eq!(cipher.seek(SeekFrom::Current(-85)).unwrap(), 5);
+    assert_eq!(read_rest(&mut cipher), &SUNSCREEN[5..]);
+
+    let end = SUNSCREEN.len() as u64;
+    assert_eq!(cipher.seek(SeekFrom::End(-4)).unwrap(), end - 4);
+    assert_eq!(read_rest(&mut cipher), &SUNSCREEN[SUNSCREEN.len() - 4..]);
+    assert_eq!(cipher.stream_position().unwrap(), end);
+
+    assert!(cipher.seek(SeekFrom::Current(-1000)).is_err());
+}
+
+#[test]
+fn initial_counter() {
+    // counter 1 is the default of `cipher`
+    let mut cipher = chacha20::cipher_with_counter(KEYFILE, NONCE, 1, SUNSCREEN);
+    assert_eq!(read_rest(&mut cipher), CIPHERTEXT);
+
+    // starting one block later gives the keystream from the second block
+    let mut cipher = chacha20::cipher_with_counter(KEYFILE, NONCE, 2, &SUNSCREEN[64..]);
+    assert_eq!(read_rest(&mut cipher), &CIPHERTEXT[64..]);
+
+    let mut keystream = chacha20::cipher_with_counter(KEYFILE, NONCE, 0, &[0; 64][..]);
+    assert_eq!(
+        read_rest(&mut keystream),
+        chacha20::block(KEYFILE, 0, NONCE)
+    );
+
+    // the keystream is limited to 2^32 blocks
+    let mut cipher = chacha20::cipher_with_counter(KEYFILE, NONCE, 1, Cursor::new(SUNSCREEN));
+    assert!(cipher.seek(SeekFrom::Start(64 << 32)).is_err());
+}
+
+#[test]
+fn seek_past_end() {
+    let mut cipher = chacha20::cipher(KEYFILE, NONCE, Cursor::new(CIPHERTEXT));
+    let end = CIPHERTEXT.len() as u64;
+
+    // inside the last block, and some blocks further
+    for target in [end + 1, end + 100] {
+        assert_eq!(cipher.seek(SeekFrom::Start(target)).unwrap(), target);
+        assert_eq!(cipher.stream_position().unwrap(), target);
+        assert_eq!(read_rest(&mut cipher), b"");
+        assert_eq!(cipher.stream_position().unwrap(), target);
+    }
+
+    assert_eq!(cipher.seek(SeekFrom::End(3)).unwrap(), end + 3);
+    assert_eq!(cipher.stream_position().unwrap(), end + 3);
+
+    // and back
+    assert_eq!(cipher.seek(SeekFrom::Current(-13)).unwrap(), end - 10);
+    assert_eq!(read_rest(&mut cipher), &SUNSCREEN[SUNSCREEN.len() - 10..]);
+}
@@ -0,0 +1,111 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use chacha20::{Cipher, Djb};
+use io_utils::hex;
+
+const KEY: [u8; 32] = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+const NONCE: u64 = 0x0706050403020100;
+
+// an endless source of zeros, which can seek anywhere
+struct Zeros;
+
+impl Read for Zeros {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        buf.fill(0);
+        Ok(buf.len())
+    }
+}
+
+impl Seek for Zeros {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match pos {
+            SeekFrom::Start(offset) => Ok(offset),
+            // an endless stream keeps no position, and has no end
+            SeekFrom::Current(_) | SeekFrom::End(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Zeros can only seek from the start",
+            )),
+        }
+    }
+}
+
+fn keystream<V: chacha20::Variant>(nonce: V::Nonce, counter: u64, len: usize) -> Vec<u8> {
+    let mut keystream = Vec::new();
+    Cipher::<_, V>::new(&KEY, nonce, counter, io::repeat(0).take(len as u64))
+        .read_to_end(&mut keystream)
+        .unwrap();
+    keystream
+}
+
+// the first block of the all-zero key and nonce is the same for both layouts
+#[test]
+fn djb_zero() {
+    let mut keystream = Vec::new();
+    chacha20::cipher_djb(&[0; 32], 0, &[0; 64][..])
+        .read_to_end(&mut keystream)
+        .unwrap();
+
+    let expected = hex!(
+        "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7\
+         da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586"
+    );
+    assert_eq!(keystream, expected);
+}
+
+// the following vectors were computed with OpenSSL, whose 16-byte IV
+// holds the 64-bit counter and nonce
+
+#[test]
+fn djb_nonce() {
+    let expected = hex!(
+        "f798a189f195e66982105ffb640bb7757f579da31602fc93ec01ac56f85ac3c1\
+         34a4547b733b46413042c9440049176905d3be59ea1c53f15916155c2be8241a\
+         38008b9a26bc35941e2444177c8ade6689de95264986d95889fb60e84629c9bd\
+         9a5acb1cc118be563eb9b3a4a472f82e09a7e778492b562ef7130e88dfe031c7"
+    );
+    assert_eq!(keystream::<Djb>(NONCE, 0, 128), expected);
+}
+
+// the counter carries into the second word, where the IETF variant would stop
+#[test]
+fn djb_counter_carry() {
+    let expected = hex!(
+        "a2b8d04b13877b4a7013cb9031e4b70836e9705a9691bd18f8fca48502eacdca\
+         e0b8faaeef6c5dfee436afd8268aa6385dabb2855761127a3946b50d649f9a4b\
+         2fcab2c09a960545c6f57e9269ebc22b4ed12782e66dc4cb612536f5cdbed4bc\
+         ba16af8a92140bf4ded4808af8eee82bd0f18fbb64f073c2a547bc2372528f36"
+    );
+    assert_eq!(keystream::<Djb>(NONCE, 0xffff_ffff, 128), expected);
+
+    // and seeking gets there too
+    let mut cipher = Cipher::<_, Djb>::new(&KEY, NONCE, 0, Zeros);
+    cipher.seek(SeekFrom::Start(0xffff_ffff * 64)).unwrap();
+    let mut block = [0; 64];
+    cipher.read_exact(&mut block).unwrap();
+    assert_eq!(block, expected[..64]);
+}
+
+#[test]
+fn ietf_exhaustion() {
+    let nonce = (0, 0x4a000000, 0);
+
+    // the last block is still available
+    let last = keystream::<chacha20::Ietf>(nonce, u32::MAX.into(), 64);
+    assert_eq!(last, chacha20::block(&KEY, u32::MAX, nonce));
+
+    let mut cipher = chacha20::cipher_with_counter(&KEY, nonce, u32::MAX, io::repeat(0));
+    let mut buf = [0; 64];
+    cipher.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, &last[..]);
+
+    let err = cipher.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+
+    // and it stays exhausted, without giving away the input
+    let err = cipher.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+
+    // the end of the input is not an error
+    let mut cipher = chacha20::cipher_with_counter(&KEY, nonce, u32::MAX, &[0; 64][..]);
+    assert_eq!(io::copy(&mut cipher, &mut io::sink()).unwrap(), 64);
+}
@@ -0,0 +1,71 @@
+use std::io::Read;
+
+use io_utils::hex;
+
+// test vectors of draft-irtf-cfrg-xchacha-03
+
+const KEY: [u8; 32] = hex!("808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f");
+
+// section 2.2.1
+#[test]
+fn hchacha20() {
+    let key = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+    let nonce = hex!("000000090000004a0000000031415927");
+
+    let expected = hex!("82413b4227b27bfed30e42508a877d73a0f9e4d58a74a853c12ec41326d3ecdc");
+    assert_eq!(chacha20::hchacha20(&key, &nonce), expected);
+}
+
+// appendix A.3.1: the encryption part of XChaCha20-Poly1305,
+// which starts at block counter 1 like `cipher`
+#[test]
+fn xcipher() {
+    const PLAINTEXT: &[u8] = include_bytes!("sunscreen.txt");
+    let nonce = chacha20::parse_xnonce("404142434445464748494a4b4c4d4e4f5051525354555657").unwrap();
+    let expected = hex!(
+        "bd6d179d3e83d43b9576579493c0e939572a1700252bfaccbed2902c21396cbb\
+         731c7f1b0b4aa6440bf3a82f4eda7e39ae64c6708c54c216cb96b72e1213b452\
+         2f8c9ba40db5d945b11b69b982c1bb9e3f3fac2bc369488f76b2383565d3fff9\
+         21f9664c97637da9768812f615c68b13b52e"
+    );
+
+    let mut ciphertext = Vec::new();
+    chacha20::xcipher(&KEY, &nonce, PLAINTEXT)
+        .read_to_end(&mut ciphertext)
+        .unwrap();
+    assert_eq!(ciphertext, expected);
+
+    let mut plaintext = Vec::new();
+    chacha20::xcipher(&KEY, &nonce, &ciphertext[..])
+        .read_to_end(&mut plaintext)
+        .unwrap();
+    assert_eq!(plaintext, PLAINTEXT);
+}
+
+// appendix A.3.2: the keystream starts at block counter 0
+#[test]
+fn xchacha20() {
+    const PLAINTEXT: &[u8] = b"The dhole (pronounced \"dole\") is also known as the Asiatic wild dog, \
+        red dog, and whistling dog. It is about the size of a German shepherd but looks more like a \
+        long-legged fox. This highly elusive and skilled jumper is classified with wolves, coyotes, \
+        jackals, and foxes in the taxonomic family Canidae.";
+    let nonce = hex!("404142434445464748494a4b4c4d4e4f5051525354555658");
+    let expected = hex!(
+        "4559abba4e48c16102e8bb2c05e6947f50a786de162f9b0b7e592a9b53d0d4e9\
+         8d8d6410d540a1a6375b26d80dace4fab52384c731acbf16a5923c0c48d3575d\
+         4d0d2c673b666faa731061277701093a6bf7a158a8864292a41c48e3a9b4c0da\
+         ece0f8d98d0d7e05b37a307bbb66333164ec9e1b24ea0d6c3ffddcec4f68e744\
+         3056193a03c810e11344ca06d8ed8a2bfb1e8d48cfa6bc0eb4e2464b74814240\
+         7c9f431aee769960e15ba8b96890466ef2457599852385c661f752ce20f9da0c\
+         09ab6b19df74e76a95967446f8d0fd415e7bee2a12a114c20eb5292ae7a349ae\
+         577820d5520a1f3fb62a17ce6a7e68fa7c79111d8860920bc048ef43fe84486c\
+         cb87c25f0ae045f0cce1e7989a9aa220a28bdd4827e751a24a6d5c62d790a663\
+         93b93111c1a55dd7421a10184974c7c5"
+    );
+
+    let mut ciphertext = Vec::new();
+    chacha20::xcipher_with_counter(&KEY, &nonce, 0, PLAINTEXT)
+        .read_to_end(&mut ciphertext)
+        .unwrap();
+    assert_eq!(ciphertext, expected);
+}
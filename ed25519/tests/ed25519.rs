@@ -81,3 +81,16 @@ fn signature_verification() {
         assert!(verify, "keys: {keys:?}");
     }
 }
+
+#[test]
+fn deterministic_keygen() {
+    let keys = ed25519::keygen(&mut shake128::ShakeDrbg::new(b"ed25519 keygen"));
+    assert_eq!(keys, ed25519::derive_key(keys.private));
+
+    let again = ed25519::keygen(&mut shake128::ShakeDrbg::new(b"ed25519 keygen"));
+    assert_eq!(keys, again);
+
+    let msg = b"signed with a key from a seeded generator";
+    let sig = ed25519::sign(&keys, Cursor::new(msg)).unwrap();
+    assert!(ed25519::verify(&keys.public, &sig, &msg[..]).unwrap());
+}
@@ -0,0 +1,91 @@
+//! HMAC, as defined in RFC 2104, over any hash function implementing the `digest` traits
+
+use std::io::{self, Read, Write};
+
+use digest::{
+    core_api::{Block, BlockSizeUser},
+    Digest, Output,
+};
+use io_utils::ct_eq;
+
+/// Incremental HMAC computation.
+#[derive(Clone)]
+pub struct Hmac<H: Digest + BlockSizeUser + Clone> {
+    inner: H,
+    outer: H,
+}
+
+impl<H: Digest + BlockSizeUser + Clone> Hmac<H> {
+    pub fn new(key: &[u8]) -> Self {
+        // keys longer than a block are hashed first
+        let mut block = Block::<H>::default();
+        if key.len() > block.len() {
+            let hashed = H::digest(key);
+            block[..hashed.len()].copy_from_slice(&hashed);
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = H::new();
+        let ipad: Vec<u8> = block.iter().map(|k| k ^ 0x36).collect();
+        inner.update(&ipad);
+
+        let mut outer = H::new();
+        let opad: Vec<u8> = block.iter().map(|k| k ^ 0x5c).collect();
+        outer.update(&opad);
+
+        Self { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> Output<H> {
+        let mut outer = self.outer;
+        outer.update(self.inner.finalize());
+        outer.finalize()
+    }
+
+    /// Checks `tag` in constant time. The tag must be a full output:
+    /// truncated tags are only accepted by [`Hmac::verify_truncated`].
+    pub fn verify(self, tag: &[u8]) -> bool {
+        self.verify_truncated(tag, <H as Digest>::output_size())
+    }
+
+    /// Checks `tag` against the first `len` bytes of the output, in constant time.
+    /// `len` is chosen by the verifier, so a forged tag can not pick its own length.
+    ///
+    /// # Panics
+    /// If `len` is less than 10 bytes (80 bits, the minimum in RFC 2104) or
+    /// more than the size of the hash output.
+    pub fn verify_truncated(self, tag: &[u8], len: usize) -> bool {
+        assert!(
+            (10..=<H as Digest>::output_size()).contains(&len),
+            "truncated HMAC tags are between 10 bytes and the hash output long"
+        );
+
+        ct_eq(&self.finalize()[..len], tag)
+    }
+}
+
+impl<H: Digest + BlockSizeUser + Clone> Write for Hmac<H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// HMAC of `msg` under `key`.
+pub fn hmac<H: Digest + BlockSizeUser + Clone>(
+    key: &[u8],
+    mut msg: impl Read,
+) -> io::Result<Output<H>> {
+    let mut mac = Hmac::<H>::new(key);
+    io::copy(&mut msg, &mut mac)?;
+    Ok(mac.finalize())
+}
@@ -0,0 +1,64 @@
+//! HMAC-based key derivation function (HKDF), as defined in RFC 5869
+
+pub mod hmac;
+
+pub use digest;
+pub use hmac::{hmac, Hmac};
+
+use digest::{core_api::BlockSizeUser, Digest, Output};
+
+/// HKDF-Extract: derives a pseudorandom key from the input keying material `ikm`.
+///
+/// An empty `salt` is replaced with a hash output of zero bytes, as in the RFC.
+pub fn extract<H: Digest + BlockSizeUser + Clone>(salt: &[u8], ikm: &[u8]) -> Output<H> {
+    let zeros = Output::<H>::default();
+    let salt = if salt.is_empty() { &zeros[..] } else { salt };
+
+    let mut mac = Hmac::<H>::new(salt);
+    mac.update(ikm);
+    mac.finalize()
+}
+
+/// HKDF-Expand: derives `len` bytes of output keying material from
+/// the pseudorandom key `prk`, bound to the context `info`.
+///
+/// # Panics
+/// If `len` is more than 255 times the size of the hash output.
+pub fn expand<H: Digest + BlockSizeUser + Clone>(prk: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+    let output_size = <H as Digest>::output_size();
+    assert!(
+        len <= 255 * output_size,
+        "HKDF output is at most 255 hash outputs long"
+    );
+
+    let mac = Hmac::<H>::new(prk);
+    let mut okm = Vec::with_capacity(len);
+    let mut t: Option<Output<H>> = None;
+
+    // T(i) = HMAC(PRK, T(i - 1) | info | i), with T(0) empty
+    for i in 1..=len.div_ceil(output_size) as u8 {
+        let mut block = mac.clone();
+        if let Some(t) = &t {
+            block.update(t);
+        }
+        block.update(info);
+        block.update(&[i]);
+
+        let output = block.finalize();
+        let n = (len - okm.len()).min(output_size);
+        okm.extend_from_slice(&output[..n]);
+        t = Some(output);
+    }
+
+    okm
+}
+
+/// HKDF-Extract then HKDF-Expand.
+pub fn hkdf<H: Digest + BlockSizeUser + Clone>(
+    salt: &[u8],
+    ikm: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Vec<u8> {
+    expand::<H>(&extract::<H>(salt, ikm), info, len)
+}
@@ -0,0 +1,119 @@
+use hkdf::digest::{core_api::BlockSizeUser, Digest};
+use hkdf::{hmac, Hmac};
+use io_utils::hex;
+use shake128::{Sha3_256Hasher, Sha3_512Hasher};
+
+// RFC 4231 test cases 1 to 7, as (key, data)
+fn test_cases() -> [(Vec<u8>, &'static [u8]); 7] {
+    [
+        (vec![0x0b; 20], b"Hi There"),
+        (b"Jefe".to_vec(), b"what do ya want for nothing?"),
+        (vec![0xaa; 20], &[0xdd; 50]),
+        ((1..=25).collect(), &[0xcd; 50]),
+        (vec![0x0c; 20], b"Test With Truncation"),
+        (
+            vec![0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+        ),
+        (
+            vec![0xaa; 131],
+            b"This is a test using a larger than block-size key and a larger than block-size data. \
+              The key needs to be hashed before being used by the HMAC algorithm.",
+        ),
+    ]
+}
+
+fn check<H: Digest + BlockSizeUser + Clone>(expected: &[&[u8]; 7]) {
+    for (i, ((key, data), expected)) in test_cases().iter().zip(expected).enumerate() {
+        let mac = hmac::<H>(key, *data).unwrap();
+        assert_eq!(&mac[..expected.len()], *expected, "test case {}", i + 1);
+
+        let mut incremental = Hmac::<H>::new(key);
+        data.chunks(7).for_each(|chunk| incremental.update(chunk));
+        assert!(
+            incremental.verify_truncated(expected, expected.len()),
+            "test case {}",
+            i + 1
+        );
+    }
+}
+
+#[test]
+fn hmac_sha256() {
+    check::<sha2::Sha256>(&[
+        &hex!("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+        &hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+        &hex!("773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe"),
+        &hex!("82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b"),
+        // truncated to 128 bits in the RFC
+        &hex!("a3b6167473100ee06e0c796c2955552b"),
+        &hex!("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"),
+        &hex!("9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2"),
+    ]);
+}
+
+#[test]
+fn hmac_sha512() {
+    check::<sha2::Sha512>(&[
+        &hex!("87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"),
+        &hex!("164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"),
+        &hex!("fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb"),
+        &hex!("b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd"),
+        &hex!("415fad6271580a531d4179bc891d87a6"),
+        &hex!("80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"),
+        &hex!("e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58"),
+    ]);
+}
+
+// the same inputs with HMAC-SHA3, checked against Python's hmac module
+#[test]
+fn hmac_sha3_256() {
+    check::<Sha3_256Hasher>(&[
+        &hex!("ba85192310dffa96e2a3a40e69774351140bb7185e1202cdcc917589f95e16bb"),
+        &hex!("c7d4072e788877ae3596bbb0da73b887c9171f93095b294ae857fbe2645e1ba5"),
+        &hex!("84ec79124a27107865cedd8bd82da9965e5ed8c37b0ac98005a7f39ed58a4207"),
+        &hex!("57366a45e2305321a4bc5aa5fe2ef8a921f6af8273d7fe7be6cfedb3f0aea6d7"),
+        &hex!("6e02c64537fb118057abb7fb66a23b3c"),
+        &hex!("ed73a374b96c005235f948032f09674a58c0ce555cfc1f223b02356560312c3b"),
+        &hex!("65c5b06d4c3de32a7aef8763261e49adb6e2293ec8e7c61e8de61701fc63e123"),
+    ]);
+}
+
+#[test]
+fn hmac_sha3_512() {
+    check::<Sha3_512Hasher>(&[
+        &hex!("eb3fbd4b2eaab8f5c504bd3a41465aacec15770a7cabac531e482f860b5ec7ba47ccb2c6f2afce8f88d22b6dc61380f23a668fd3888bb80537c0a0b86407689e"),
+        &hex!("5a4bfeab6166427c7a3647b747292b8384537cdb89afb3bf5665e4c5e709350b287baec921fd7ca0ee7a0c31d022a95e1fc92ba9d77df883960275beb4e62024"),
+        &hex!("309e99f9ec075ec6c6d475eda1180687fcf1531195802a99b5677449a8625182851cb332afb6a89c411325fbcbcd42afcb7b6e5aab7ea42c660f97fd8584bf03"),
+        &hex!("b27eab1d6e8d87461c29f7f5739dd58e98aa35f8e823ad38c5492a2088fa0281993bbfff9a0e9c6bf121ae9ec9bb09d84a5ebac817182ea974673fb133ca0d1d"),
+        &hex!("0fa7475948f43f48ca0516671e18978c"),
+        &hex!("00f751a9e50695b090ed6911a4b65524951cdc15a73a5d58bb55215ea2cd839ac79d2b44a39bafab27e83fde9e11f6340b11d991b1b91bf2eee7fc872426c3a4"),
+        &hex!("38a456a004bd10d32c9ab8336684112862c3db61adcca31829355eaf46fd5c73d06a1f0d13fec9a652fb3811b577b1b1d1b9789f97ae5b83c6f44dfcf1d67eba"),
+    ]);
+}
+
+#[test]
+fn reject_wrong_tags() {
+    let mac = Hmac::<sha2::Sha256>::new(b"key");
+    let tag = mac.clone().finalize();
+
+    let mut wrong = tag;
+    wrong[31] ^= 1;
+    assert!(!mac.clone().verify(&wrong));
+    assert!(!mac.clone().verify(&[]));
+
+    // prefixes are only accepted at the length required by the verifier
+    assert!(!mac.clone().verify(&tag[..1]));
+    assert!(!mac.clone().verify(&tag[..16]));
+    assert!(!mac.clone().verify_truncated(&tag[..1], 16));
+    assert!(mac.clone().verify_truncated(&tag[..16], 16));
+    assert!(mac.verify(&tag));
+}
+
+#[test]
+#[should_panic]
+fn reject_short_truncation() {
+    let mac = Hmac::<sha2::Sha256>::new(b"key");
+    let tag = mac.clone().finalize();
+    mac.verify_truncated(&tag[..1], 1);
+}
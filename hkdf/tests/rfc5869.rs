@@ -0,0 +1,92 @@
+use hkdf::{expand, extract, hkdf};
+use io_utils::hex;
+use shake128::Sha3_256Hasher;
+
+// RFC 5869 test cases 1 to 3 (HKDF-SHA256)
+
+#[test]
+fn basic() {
+    let ikm = [0x0b; 22];
+    let salt: Vec<u8> = (0x00..=0x0c).collect();
+    let info: Vec<u8> = (0xf0..=0xf9).collect();
+
+    let prk = extract::<sha2::Sha256>(&salt, &ikm);
+    assert_eq!(
+        prk.as_slice(),
+        hex!("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+    );
+
+    let okm = expand::<sha2::Sha256>(&prk, &info, 42);
+    assert_eq!(
+        okm,
+        hex!(
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        )
+    );
+}
+
+#[test]
+fn longer_inputs() {
+    let ikm: Vec<u8> = (0x00..=0x4f).collect();
+    let salt: Vec<u8> = (0x60..=0xaf).collect();
+    let info: Vec<u8> = (0xb0..=0xff).collect();
+
+    let prk = extract::<sha2::Sha256>(&salt, &ikm);
+    assert_eq!(
+        prk.as_slice(),
+        hex!("06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244")
+    );
+
+    let okm = expand::<sha2::Sha256>(&prk, &info, 82);
+    assert_eq!(
+        okm,
+        hex!("b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71cc30c58179ec3e87c14c01d5c1f3434f1d87")
+    );
+}
+
+#[test]
+fn empty_salt_and_info() {
+    let ikm = [0x0b; 22];
+
+    let prk = extract::<sha2::Sha256>(b"", &ikm);
+    assert_eq!(
+        prk.as_slice(),
+        hex!("19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04")
+    );
+
+    let okm = hkdf::<sha2::Sha256>(b"", &ikm, b"", 42);
+    assert_eq!(
+        okm,
+        hex!(
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+        )
+    );
+}
+
+// checked against a Python implementation over hashlib's SHA3-256
+#[test]
+fn hkdf_sha3_256() {
+    let okm = hkdf::<Sha3_256Hasher>(b"salt", b"ikm", b"info", 100);
+    assert_eq!(
+        okm,
+        hex!("0a35ce133619c6dda6c47247ba3a65352b8fa7313a3ccddfc707ea425085517cbcaa618df8674ec883418dd1ee1863214223b4116e2632abfe396a7e366b2b027234eadd966af0c89a9f467d22a3d4fa68f93fe02a197ca65d0e87abf6fef623415ec004")
+    );
+}
+
+#[test]
+fn output_prefixes() {
+    // shorter outputs are prefixes of longer ones
+    let long = hkdf::<sha2::Sha512>(b"salt", b"ikm", b"info", 255 * 64);
+    for len in [0, 1, 63, 64, 65, 1000] {
+        assert_eq!(
+            hkdf::<sha2::Sha512>(b"salt", b"ikm", b"info", len),
+            long[..len]
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn output_too_long() {
+    hkdf::<sha2::Sha256>(b"salt", b"ikm", b"info", 255 * 32 + 1);
+}
@@ -1,5 +1,6 @@
 use std::{
     fmt::{Binary, Debug, Display},
+    hint::black_box,
     io::{self, ErrorKind, Read, Write},
 };
 
@@ -159,3 +160,16 @@ pub fn hexfmt(digest: &impl AsRef<[u8]>) -> impl Display + Debug + '_ {
 
     Digest(digest.as_ref())
 }
+
+/// Compares two byte strings in a time that only depends on their lengths,
+/// to check MAC tags without leaking how many leading bytes match.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    // reduce the xored bytes to a logical OR, compared once to zero at the end.
+    // black box prevents the compiler from short-circuiting the fold
+    a.len() == b.len()
+        && black_box(
+            a.iter()
+                .zip(b)
+                .fold(0, |acc, (x, y)| black_box(acc | (x ^ y))),
+        ) == 0
+}
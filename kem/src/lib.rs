@@ -352,4 +352,18 @@ mod tests {
 
         assert_eq!(key1, key2)
     }
+
+    #[test]
+    fn deterministic_rng() {
+        use shake128::ShakeDrbg;
+
+        let (public, private) = keygen(&mut ShakeDrbg::new(b"kem keygen"));
+        let (cipher, key1) = encaps(&mut ShakeDrbg::new(b"kem encaps"), &public);
+        assert_eq!(decaps(&cipher, &private), key1);
+
+        // the same seeds give the same keys and ciphertext
+        let (public2, _) = keygen(&mut ShakeDrbg::new(b"kem keygen"));
+        let (cipher2, key2) = encaps(&mut ShakeDrbg::new(b"kem encaps"), &public2);
+        assert_eq!((public2, cipher2, key2), (public, cipher, key1));
+    }
 }
@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use poly1305::{poly1305, poly1305_reference, Backend, Poly1305};
+
+fn tag(c: &mut Criterion) {
+    let key = [0x5a; 32];
+
+    for len in [64, 1 << 20] {
+        let msg = vec![0xa5; len];
+
+        let mut group = c.benchmark_group(format!("poly1305 of {len} bytes"));
+        group.throughput(Throughput::Bytes(len as u64));
+
+        group.bench_function("radix 2^26", |b| {
+            b.iter(|| poly1305(black_box(msg.as_slice()), &key).unwrap())
+        });
+        group.bench_function("reference", |b| {
+            b.iter(|| poly1305_reference(black_box(msg.as_slice()), &key).unwrap())
+        });
+
+        group.finish();
+    }
+}
+
+// the loops over long messages, including those not picked on this CPU
+fn backends(c: &mut Criterion) {
+    let key = [0x5a; 32];
+    let msg = vec![0xa5; 1 << 20];
+
+    let mut backends = vec![Backend::OneBlock, Backend::Lanes];
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        backends.push(Backend::Avx2);
+    }
+
+    let mut group = c.benchmark_group("poly1305 backends");
+    group.throughput(Throughput::Bytes(msg.len() as u64));
+    for backend in backends {
+        group.bench_function(format!("{backend:?}"), |b| {
+            b.iter(|| {
+                let mut mac = Poly1305::with_backend(&key, backend);
+                mac.update(black_box(&msg));
+                mac.finalize()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tag, backends);
+criterion_main!(benches);
@@ -0,0 +1,177 @@
+//! Poly1305 over four blocks at a time, with AVX2
+//!
+//! Four accumulators absorb interleaved blocks, and are multiplied by r^4 after
+//! each block. The last multiplication is by r^4, r^3, r^2 and r instead, so that
+//! the sum of the accumulators is the sequential result:
+//! ((h + m0) r^4 + m4) r^4 + (m1 r^4 + m5) r^3 + (m2 r^4 + m6) r^2 + (m3 r^4 + m7) r.
+//!
+//! Each 256-bit vector holds the same 26-bit limb of the four accumulators,
+//! in 64-bit lanes, so that the products are computed four at a time.
+
+use std::arch::x86_64::*;
+
+use crate::radix26::{mul, times5};
+
+// limbs of four integers, in 64-bit lanes
+type Limbs = [[u64; 4]; 5];
+
+/// Powers of r, with the same limb in each lane.
+#[derive(Clone)]
+pub struct Powers {
+    r4: Limbs,
+    s4: [[u64; 4]; 4],
+    // lanes hold blocks in the order 0, 2, 1, 3 (see `load`)
+    last_r: Limbs,
+    last_s: [[u64; 4]; 4],
+}
+
+impl Powers {
+    pub fn new(r: &[u32; 5]) -> Self {
+        let s = times5(r);
+        let r2 = mul(*r, r, &s);
+        let r3 = mul(r2, r, &s);
+        let r4 = mul(r2, &r2, &times5(&r2));
+
+        let spread = |limbs: [[u32; 5]; 4]| -> Limbs {
+            std::array::from_fn(|i| limbs.map(|limb| limb[i] as u64))
+        };
+        let spread5 = |limbs: [[u32; 5]; 4]| -> [[u64; 4]; 4] {
+            let limbs = spread(limbs);
+            std::array::from_fn(|i| limbs[i + 1].map(|x| x * 5))
+        };
+
+        Self {
+            r4: spread([r4; 4]),
+            s4: spread5([r4; 4]),
+            last_r: spread([r4, r2, r3, *r]),
+            last_s: spread5([r4, r2, r3, *r]),
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn load_vectors<const N: usize>(limbs: &[[u64; 4]; N]) -> [__m256i; N] {
+    limbs.map(|limb| _mm256_loadu_si256(limb.as_ptr().cast()))
+}
+
+// the four 16-byte blocks of `chunk`, split in 26-bit limbs.
+// The unpacking puts the blocks in the order 0, 2, 1, 3 in the lanes
+#[inline(always)]
+unsafe fn load(chunk: &[u8]) -> [__m256i; 5] {
+    let mask = _mm256_set1_epi64x((1 << 26) - 1);
+
+    let a = _mm256_loadu_si256(chunk.as_ptr().cast());
+    let b = _mm256_loadu_si256(chunk[32..].as_ptr().cast());
+    let lo = _mm256_unpacklo_epi64(a, b);
+    let hi = _mm256_unpackhi_epi64(a, b);
+
+    [
+        _mm256_and_si256(lo, mask),
+        _mm256_and_si256(_mm256_srli_epi64(lo, 26), mask),
+        _mm256_and_si256(
+            _mm256_or_si256(_mm256_srli_epi64(lo, 52), _mm256_slli_epi64(hi, 12)),
+            mask,
+        ),
+        _mm256_and_si256(_mm256_srli_epi64(hi, 14), mask),
+        _mm256_or_si256(_mm256_srli_epi64(hi, 40), _mm256_set1_epi64x(1 << 24)),
+    ]
+}
+
+// h * r in each lane, partially reduced like `radix26::mul`
+#[inline(always)]
+unsafe fn mul4(h: &[__m256i; 5], r: &[__m256i; 5], s: &[__m256i; 4]) -> [__m256i; 5] {
+    let mask = _mm256_set1_epi64x((1 << 26) - 1);
+    let dot = |terms: [(__m256i, __m256i); 5]| {
+        terms
+            .into_iter()
+            .map(|(x, y)| _mm256_mul_epu32(x, y))
+            .reduce(|x, y| _mm256_add_epi64(x, y))
+            .unwrap()
+    };
+
+    let [h0, h1, h2, h3, h4] = *h;
+    let [r0, r1, r2, r3, r4] = *r;
+    let [s1, s2, s3, s4] = *s;
+
+    let d0 = dot([(h0, r0), (h1, s4), (h2, s3), (h3, s2), (h4, s1)]);
+    let mut d1 = dot([(h0, r1), (h1, r0), (h2, s4), (h3, s3), (h4, s2)]);
+    let mut d2 = dot([(h0, r2), (h1, r1), (h2, r0), (h3, s4), (h4, s3)]);
+    let mut d3 = dot([(h0, r3), (h1, r2), (h2, r1), (h3, r0), (h4, s4)]);
+    let mut d4 = dot([(h0, r4), (h1, r3), (h2, r2), (h3, r1), (h4, r0)]);
+
+    d1 = _mm256_add_epi64(d1, _mm256_srli_epi64(d0, 26));
+    d2 = _mm256_add_epi64(d2, _mm256_srli_epi64(d1, 26));
+    d3 = _mm256_add_epi64(d3, _mm256_srli_epi64(d2, 26));
+    d4 = _mm256_add_epi64(d4, _mm256_srli_epi64(d3, 26));
+
+    // c * 5 = c + 4c
+    let c = _mm256_srli_epi64(d4, 26);
+    let h0 = _mm256_add_epi64(
+        _mm256_and_si256(d0, mask),
+        _mm256_add_epi64(c, _mm256_slli_epi64(c, 2)),
+    );
+
+    [
+        _mm256_and_si256(h0, mask),
+        _mm256_add_epi64(_mm256_and_si256(d1, mask), _mm256_srli_epi64(h0, 26)),
+        _mm256_and_si256(d2, mask),
+        _mm256_and_si256(d3, mask),
+        _mm256_and_si256(d4, mask),
+    ]
+}
+
+/// Processes whole groups of four blocks: `data.len()` must be a multiple of 64.
+///
+/// # Safety
+///
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn blocks(h: &mut [u32; 5], powers: &Powers, data: &[u8]) {
+    debug_assert!(data.len().is_multiple_of(64));
+    let chunks = data.chunks_exact(64);
+    let count = chunks.len();
+    if count == 0 {
+        return;
+    }
+
+    let r4 = load_vectors(&powers.r4);
+    let s4 = load_vectors(&powers.s4);
+    let last_r = load_vectors(&powers.last_r);
+    let last_s = load_vectors(&powers.last_s);
+
+    // the current accumulator goes in the first lane
+    let mut acc = load_vectors(&h.map(|limb| [limb as u64, 0, 0, 0]));
+
+    for (i, chunk) in chunks.enumerate() {
+        let m = load(chunk);
+        for (acc, m) in acc.iter_mut().zip(m) {
+            *acc = _mm256_add_epi64(*acc, m);
+        }
+
+        acc = if i + 1 == count {
+            mul4(&acc, &last_r, &last_s)
+        } else {
+            mul4(&acc, &r4, &s4)
+        };
+    }
+
+    // sum of the lanes, brought back to 26-bit limbs
+    let mut sum = [0u64; 5];
+    for (sum, acc) in sum.iter_mut().zip(acc) {
+        let mut lanes = [0u64; 4];
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), acc);
+        *sum = lanes.iter().sum();
+    }
+
+    let mask = (1 << 26) - 1;
+    for i in 0..4 {
+        sum[i + 1] += sum[i] >> 26;
+        sum[i] &= mask;
+    }
+    sum[0] += (sum[4] >> 26) * 5;
+    sum[4] &= mask;
+    sum[1] += sum[0] >> 26;
+    sum[0] &= mask;
+
+    *h = sum.map(|limb| limb as u32);
+}
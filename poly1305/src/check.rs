@@ -1,8 +1,9 @@
 use std::fs::File;
-use std::io::BufReader;
+use std::io;
 use std::path::PathBuf;
 
 use argh::FromArgs;
+use poly1305::Poly1305;
 
 /// Check Poly1305 tags
 #[derive(FromArgs)]
@@ -15,18 +16,17 @@ pub struct Opts {
     file: PathBuf,
     /// a 32 character hexadecimal string
     #[argh(positional, from_str_fn(poly1305::parse_tag))]
-    tag: u128,
+    tag: [u8; 16],
 }
 
 fn main() {
     let opts: Opts = argh::from_env();
 
-    let file = File::open(opts.file).expect("Could not open file");
-    let file = BufReader::new(file);
+    let mut file = File::open(opts.file).expect("Could not open file");
+    let mut mac = Poly1305::new(&opts.key);
+    io::copy(&mut file, &mut mac).expect("Error while reading from file");
 
-    let poly1305 = poly1305::poly1305(file, &opts.key).expect("Error while reading from file");
-
-    if poly1305 == opts.tag {
+    if mac.verify(&opts.tag).is_ok() {
         println!("ACCEPT");
     } else {
         println!("REJECT");
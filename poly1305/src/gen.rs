@@ -1,6 +1,7 @@
-use std::{fs::File, io::BufReader, path::PathBuf};
+use std::{fs::File, io, path::PathBuf};
 
 use argh::FromArgs;
+use poly1305::Poly1305;
 
 /// Generate Poly1305 tags
 #[derive(FromArgs)]
@@ -16,12 +17,11 @@ pub struct Opts {
 fn main() {
     let opts: Opts = argh::from_env();
 
-    let file = File::open(opts.file).expect("Could not open file");
-    let file = BufReader::new(file);
+    let mut file = File::open(opts.file).expect("Could not open file");
+    let mut mac = Poly1305::new(&opts.key);
+    io::copy(&mut file, &mut mac).expect("Error while reading from file");
 
-    let poly1305 = poly1305::poly1305(file, &opts.key).expect("Error while reading from file");
-
-    for byte in poly1305.to_le_bytes() {
+    for byte in mac.finalize() {
         print!("{byte:02x}");
     }
     println!();
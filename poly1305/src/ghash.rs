@@ -0,0 +1,61 @@
+//! GHASH, the universal hash of AES-GCM (NIST SP 800-38D)
+//!
+//! GHASH works in the same field as POLYVAL, but with big-endian blocks and
+//! bit-reflected polynomials. It is computed with POLYVAL on byte-reversed
+//! blocks, and a key multiplied by x (RFC 8452, appendix A).
+
+use crate::polyval::{Polyval, X128};
+use crate::UniversalHash;
+
+/// GHASH, keyed with a 16-byte hash subkey H.
+#[derive(Clone)]
+pub struct Ghash(Polyval);
+
+// h * x
+fn mul_x(h: u128) -> u128 {
+    // all ones when the top bit is set
+    let carry = 0u128.wrapping_sub(h >> 127);
+    (h << 1) ^ (carry & X128)
+}
+
+impl Ghash {
+    pub fn new(key: &[u8; 16]) -> Self {
+        Self(Polyval::from_element(mul_x(u128::from_be_bytes(*key))))
+    }
+
+    /// Absorbs `data`, padded with zeros to a multiple of 16 bytes.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        // byte-reversed blocks, processed by batches
+        let mut buf = [0; 1024];
+        for chunk in data.chunks(buf.len()) {
+            let len = chunk.len().next_multiple_of(16);
+            buf[..len].fill(0);
+            buf[..chunk.len()].copy_from_slice(chunk);
+
+            for block in buf[..len].chunks_exact_mut(16) {
+                block.reverse();
+            }
+            self.0.update_padded(&buf[..len]);
+        }
+    }
+
+    pub fn finalize(self) -> [u8; 16] {
+        self.0.element().to_be_bytes()
+    }
+}
+
+impl UniversalHash for Ghash {
+    type Key = [u8; 16];
+
+    fn new(key: &[u8; 16]) -> Self {
+        Self::new(key)
+    }
+
+    fn update_padded(&mut self, data: &[u8]) {
+        self.update_padded(data);
+    }
+
+    fn finalize(self) -> [u8; 16] {
+        self.finalize()
+    }
+}
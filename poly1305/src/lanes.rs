@@ -0,0 +1,143 @@
+//! Poly1305 over four blocks at a time, without SIMD intrinsics
+//!
+//! The same scheme as `avx2`: four accumulators absorb interleaved blocks, and are
+//! multiplied by r^4 after each block, then by r^4, r^3, r^2 and r after the last one.
+//!
+//! Without vectors, the products of the four lanes are not computed together: the
+//! lanes use three 44-bit limbs instead, multiplied in `u128`s, which takes 9
+//! multiplications per block instead of 25. The four lanes do not depend on each
+//! other, so the CPU can run their multiplications in parallel.
+
+use crate::radix26::MASK;
+
+const MASK44: u64 = (1 << 44) - 1;
+const MASK42: u64 = (1 << 42) - 1;
+
+fn le64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+// 20 * r[1..], for the limbs that wrap around: 2^132 = 4 * 5 mod p
+fn times20(r: &[u64; 3]) -> [u64; 2] {
+    [r[1] * 20, r[2] * 20]
+}
+
+// h * r, with `s` = times20(r), partially reduced: h[1] can be just above 44 bits
+#[inline(always)]
+fn mul(h: [u64; 3], r: &[u64; 3], s: &[u64; 2]) -> [u64; 3] {
+    let [h0, h1, h2] = [h[0] as u128, h[1] as u128, h[2] as u128];
+    let [r0, r1, r2] = [r[0] as u128, r[1] as u128, r[2] as u128];
+    let [s1, s2] = [s[0] as u128, s[1] as u128];
+
+    let d0 = h0 * r0 + h1 * s2 + h2 * s1;
+    let mut d1 = h0 * r1 + h1 * r0 + h2 * s2;
+    let mut d2 = h0 * r2 + h1 * r1 + h2 * r0;
+
+    d1 += d0 >> 44;
+    d2 += d1 >> 44;
+    let h0 = (d0 as u64 & MASK44) + (d2 >> 42) as u64 * 5;
+
+    [
+        h0 & MASK44,
+        (d1 as u64 & MASK44) + (h0 >> 44),
+        d2 as u64 & MASK42,
+    ]
+}
+
+// 26-bit limbs to 44-bit limbs. The limbs are added rather than ORed,
+// so that the carries of partially reduced limbs are kept
+fn from_radix26(h: &[u32; 5]) -> [u64; 3] {
+    let [h0, h1, h2, h3, h4] = h.map(u64::from);
+
+    let x0 = h0 + (h1 << 26);
+    let x1 = (x0 >> 44) + (h2 << 8) + (h3 << 34);
+    let x2 = (x1 >> 44) + (h4 << 16);
+    [x0 & MASK44, x1 & MASK44, x2]
+}
+
+// 44-bit limbs to 26-bit limbs, keeping the carries in the same way
+fn to_radix26(h: &[u64; 3]) -> [u32; 5] {
+    let mask = MASK as u64;
+
+    let x1 = (h[0] >> 26) + (h[1] << 18);
+    let x2 = x1 >> 26;
+    let x3 = (x2 >> 26) + (h[2] << 10);
+    [h[0] & mask, x1 & mask, x2 & mask, x3 & mask, x3 >> 26].map(|limb| limb as u32)
+}
+
+/// Powers of r, in 44-bit limbs.
+#[derive(Clone)]
+pub struct Powers {
+    r4: [u64; 3],
+    s4: [u64; 2],
+    last_r: [[u64; 3]; 4],
+    last_s: [[u64; 2]; 4],
+}
+
+impl Powers {
+    pub fn new(r: &[u32; 5]) -> Self {
+        let r = from_radix26(r);
+        let s = times20(&r);
+        let r2 = mul(r, &r, &s);
+        let r3 = mul(r2, &r, &s);
+        let r4 = mul(r2, &r2, &times20(&r2));
+
+        let last_r = [r4, r3, r2, r];
+        Self {
+            r4,
+            s4: times20(&r4),
+            last_r,
+            last_s: last_r.map(|r| times20(&r)),
+        }
+    }
+}
+
+/// Processes whole groups of four blocks: `data.len()` must be a multiple of 64.
+pub fn blocks(h: &mut [u32; 5], powers: &Powers, data: &[u8]) {
+    debug_assert!(data.len().is_multiple_of(64));
+    let chunks = data.chunks_exact(64);
+    let count = chunks.len();
+    if count == 0 {
+        return;
+    }
+
+    // the current accumulator goes in the first lane
+    let mut acc = [from_radix26(h), [0; 3], [0; 3], [0; 3]];
+
+    // plain loops rather than array maps, which are not always inlined
+    for (i, chunk) in chunks.enumerate() {
+        let last = i + 1 == count;
+        for (l, block) in chunk.chunks_exact(16).enumerate() {
+            let (t0, t1) = (le64(&block[0..]), le64(&block[8..]));
+            let h = [
+                acc[l][0] + (t0 & MASK44),
+                acc[l][1] + ((t0 >> 44 | t1 << 20) & MASK44),
+                acc[l][2] + (t1 >> 24 | 1 << 40),
+            ];
+
+            acc[l] = if last {
+                mul(h, &powers.last_r[l], &powers.last_s[l])
+            } else {
+                mul(h, &powers.r4, &powers.s4)
+            };
+        }
+    }
+
+    // sum of the lanes, brought back to 44-bit limbs
+    let mut sum = [0; 3];
+    for lane in acc {
+        for (sum, limb) in sum.iter_mut().zip(lane) {
+            *sum += limb;
+        }
+    }
+    sum[1] += sum[0] >> 44;
+    sum[0] &= MASK44;
+    sum[2] += sum[1] >> 44;
+    sum[1] &= MASK44;
+    sum[0] += (sum[2] >> 42) * 5;
+    sum[2] &= MASK42;
+    sum[1] += sum[0] >> 44;
+    sum[0] &= MASK44;
+
+    *h = to_radix26(&sum);
+}
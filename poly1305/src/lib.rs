@@ -1,54 +1,200 @@
-use crypto_bigint::{impl_modulus, modular::constant_mod::Residue, Encoding, U192};
-use io_utils::{parse_hex, ReadExt};
-use std::io::{self, Read};
+use io_utils::{ct_eq, parse_hex, ReadExt};
+use std::fmt;
+use std::io::{self, Read, Write};
 
-impl_modulus!(
-    P1305,
-    U192,
-    "0000000000000003fffffffffffffffffffffffffffffffb"
-);
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+mod ghash;
+mod lanes;
+mod polyval;
+mod radix26;
+mod reference;
 
-type Fp = Residue<P1305, 3>;
+pub use ghash::Ghash;
+pub use polyval::Polyval;
+#[doc(hidden)]
+pub use radix26::Backend;
+pub use reference::poly1305_reference;
+
+// amount of data read from the input before processing it
+const BUF_SIZE: usize = 1 << 12;
 
 fn clamp(r: u128) -> u128 {
     const MASK: u128 = 0xFFFFFFC0FFFFFFC0FFFFFFC0FFFFFFF;
     r & MASK
 }
 
-pub fn poly1305(mut data: impl Read, key: &[u8; 32]) -> io::Result<u128> {
-    let r = u128::from_le_bytes(key[..16].try_into().unwrap());
-    let s = u128::from_le_bytes(key[16..].try_into().unwrap());
+/// Error returned when a tag does not match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MacError;
+
+impl fmt::Display for MacError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("MAC tag mismatch")
+    }
+}
+
+impl std::error::Error for MacError {}
+
+/// A universal hash function producing 16-byte tags: Poly1305, GHASH or POLYVAL.
+///
+/// The messages are absorbed by pieces padded with zeros to whole 16-byte blocks,
+/// as done by the AEAD constructions.
+pub trait UniversalHash: Sized {
+    type Key;
+
+    fn new(key: &Self::Key) -> Self;
+
+    /// Absorbs `data`, padded with zeros to a multiple of 16 bytes.
+    fn update_padded(&mut self, data: &[u8]);
+
+    fn finalize(self) -> [u8; 16];
+
+    /// Checks the tag in constant time.
+    fn verify(self, tag: &[u8; 16]) -> Result<(), MacError> {
+        if ct_eq(&self.finalize(), tag) {
+            Ok(())
+        } else {
+            Err(MacError)
+        }
+    }
+}
+
+/// Incremental Poly1305 computation.
+///
+/// The message can be given in pieces of any length: bytes are buffered
+/// until a whole 16-byte block is available.
+#[derive(Clone)]
+pub struct Poly1305 {
+    state: radix26::State,
+    buf: [u8; 16],
+    len: usize,
+}
+
+impl Poly1305 {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self {
+            state: radix26::State::new(key),
+            buf: [0; 16],
+            len: 0,
+        }
+    }
+
+    /// Poly1305 with the loop over long messages forced to `backend`,
+    /// to compare the implementations in the tests and benchmarks.
+    ///
+    /// # Panics
+    /// If `backend` is AVX2 and the CPU does not support it.
+    #[doc(hidden)]
+    pub fn with_backend(key: &[u8; 32], backend: Backend) -> Self {
+        Self {
+            state: radix26::State::with_backend(key, backend),
+            buf: [0; 16],
+            len: 0,
+        }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        // complete the buffered block first
+        if self.len > 0 {
+            let n = data.len().min(16 - self.len);
+            self.buf[self.len..self.len + n].copy_from_slice(&data[..n]);
+            self.len += n;
+            data = &data[n..];
+
+            if self.len < 16 {
+                return;
+            }
+            self.state.blocks(&self.buf);
+            self.len = 0;
+        }
+
+        let full = data.len() - data.len() % 16;
+        self.state.blocks(&data[..full]);
+
+        let rest = &data[full..];
+        self.buf[..rest.len()].copy_from_slice(rest);
+        self.len = rest.len();
+    }
+
+    /// Absorbs `data`, then pads the message with zeros to a multiple of 16 bytes.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        self.update(data);
+        if self.len > 0 {
+            self.buf[self.len..].fill(0);
+            self.state.blocks(&self.buf);
+            self.len = 0;
+        }
+    }
+
+    pub fn finalize(mut self) -> [u8; 16] {
+        self.state.last_block(&self.buf[..self.len]);
+        self.state.finalize().to_le_bytes()
+    }
+
+    /// Checks the tag of the message in constant time.
+    pub fn verify(self, tag: &[u8; 16]) -> Result<(), MacError> {
+        if ct_eq(&self.finalize(), tag) {
+            Ok(())
+        } else {
+            Err(MacError)
+        }
+    }
+}
+
+impl Write for Poly1305 {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl UniversalHash for Poly1305 {
+    type Key = [u8; 32];
+
+    fn new(key: &[u8; 32]) -> Self {
+        Self::new(key)
+    }
 
-    let r = clamp(r);
-    let r = Fp::new(&U192::from(r));
+    fn update_padded(&mut self, data: &[u8]) {
+        self.update_padded(data);
+    }
+
+    fn finalize(self) -> [u8; 16] {
+        self.finalize()
+    }
+
+    fn verify(self, tag: &[u8; 16]) -> Result<(), MacError> {
+        self.verify(tag)
+    }
+}
 
-    let mut acc = Fp::ZERO;
-    let mut buf;
+pub fn poly1305(mut data: impl Read, key: &[u8; 32]) -> io::Result<u128> {
+    let mut mac = Poly1305::new(key);
+    let mut buf = [0; BUF_SIZE];
 
     loop {
-        buf = [0; 24];
-        let x = data.read_all(&mut buf[..16])?;
-        if x == 0 {
+        let n = data.read_all(&mut buf)?;
+        mac.update(&buf[..n]);
+
+        if n < buf.len() {
             break;
         }
-        buf[x] = 1;
-        let n = Fp::new(&U192::from_le_bytes(buf));
-        acc = (acc + n) * r;
     }
-    acc += Fp::new(&U192::from(s));
 
-    let bytes = acc.retrieve().to_le_bytes()[..16].try_into().unwrap();
-    Ok(u128::from_le_bytes(bytes))
+    Ok(u128::from_le_bytes(mac.finalize()))
 }
 
 pub fn parse_key(arg: &str) -> Result<[u8; 32], String> {
     parse_hex::<32>(arg).ok_or_else(|| "expected 32-bytes hex string".into())
 }
 
-pub fn parse_tag(arg: &str) -> Result<u128, String> {
-    parse_hex::<16>(arg)
-        .map(u128::from_le_bytes)
-        .ok_or_else(|| "expected 16-bytes hex string".into())
+pub fn parse_tag(arg: &str) -> Result<[u8; 16], String> {
+    parse_hex::<16>(arg).ok_or_else(|| "expected 16-bytes hex string".into())
 }
 
 #[cfg(test)]
@@ -61,5 +207,21 @@ fn poly1305_tag() {
 
     let computed_tag = poly1305(TEXT, &key).unwrap();
 
-    assert_eq!(tag, computed_tag);
+    assert_eq!(tag, computed_tag.to_le_bytes());
+}
+
+#[cfg(test)]
+#[test]
+fn poly1305_verify() {
+    let key = io_utils::hex!("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b");
+    let tag = io_utils::hex!("a8061dc1305136c6c22b8baf0c0127a9");
+
+    let mut mac = Poly1305::new(&key);
+    mac.update(b"Cryptographic ");
+    mac.update(b"Forum Research Group");
+    assert_eq!(mac.clone().verify(&tag), Ok(()));
+
+    let mut forged = tag;
+    forged[15] ^= 1;
+    assert_eq!(mac.verify(&forged), Err(MacError));
 }
@@ -0,0 +1,178 @@
+//! POLYVAL, the universal hash of AES-GCM-SIV (RFC 8452)
+//!
+//! Field elements are 16-byte blocks read as little-endian integers, with bit i
+//! the coefficient of x^i, modulo x^128 + x^127 + x^126 + x^121 + 1.
+//! The products are computed with PCLMULQDQ when the CPU supports it, and
+//! otherwise with integer multiplications masking out the carries. Neither
+//! depends on the values multiplied.
+
+use crate::UniversalHash;
+
+// x^128 mod x^128 + x^127 + x^126 + x^121 + 1
+pub(crate) const X128: u128 = 0xc200_0000_0000_0000_0000_0000_0000_0001;
+
+// low 64 bits of the carry-less product of x and y.
+// Bits are split in four interleaved groups, so that the carries of the sums of
+// at most 15 products land in the holes between bits of the same group.
+#[inline(always)]
+fn bmul64(x: u64, y: u64) -> u64 {
+    const M: [u64; 4] = [
+        0x1111_1111_1111_1111,
+        0x2222_2222_2222_2222,
+        0x4444_4444_4444_4444,
+        0x8888_8888_8888_8888,
+    ];
+    let x = M.map(|m| x & m);
+    let y = M.map(|m| y & m);
+
+    let mut z = 0;
+    for (i, m) in M.iter().enumerate() {
+        let zi = (0..4).fold(0, |acc, j| acc ^ x[j].wrapping_mul(y[(4 + i - j) % 4]));
+        z |= zi & m;
+    }
+    z
+}
+
+// carry-less product of x and y.
+// The high half is the low half of the product of the reversed operands, reversed.
+#[inline(always)]
+fn clmul_soft(x: u64, y: u64) -> u128 {
+    let lo = bmul64(x, y);
+    let hi = bmul64(x.reverse_bits(), y.reverse_bits()).reverse_bits() >> 1;
+    (hi as u128) << 64 | lo as u128
+}
+
+// a * b * x^-128, the POLYVAL "dot" operation
+#[inline(always)]
+fn dot(a: u128, b: u128, clmul: impl Fn(u64, u64) -> u128) -> u128 {
+    let (a0, a1) = (a as u64, (a >> 64) as u64);
+    let (b0, b1) = (b as u64, (b >> 64) as u64);
+
+    // Karatsuba
+    let lo = clmul(a0, b0);
+    let hi = clmul(a1, b1);
+    let mid = clmul(a0 ^ a1, b0 ^ b1) ^ lo ^ hi;
+
+    let v0 = lo as u64;
+    let mut v1 = (lo >> 64) as u64 ^ mid as u64;
+    let mut v2 = hi as u64 ^ (mid >> 64) as u64;
+    let mut v3 = (hi >> 64) as u64;
+
+    // Montgomery reduction, 64 bits at a time
+    v2 ^= v0 ^ (v0 >> 1) ^ (v0 >> 2) ^ (v0 >> 7);
+    v1 ^= (v0 << 63) ^ (v0 << 62) ^ (v0 << 57);
+    v3 ^= v1 ^ (v1 >> 1) ^ (v1 >> 2) ^ (v1 >> 7);
+    v2 ^= (v1 << 63) ^ (v1 << 62) ^ (v1 << 57);
+
+    (v3 as u128) << 64 | v2 as u128
+}
+
+#[inline(always)]
+fn blocks_with(s: &mut u128, h: u128, data: &[u8], clmul: impl Fn(u64, u64) -> u128 + Copy) {
+    for block in data.chunks_exact(16) {
+        let x = u128::from_le_bytes(block.try_into().unwrap());
+        *s = dot(*s ^ x, h, clmul);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn blocks_pclmul(s: &mut u128, h: u128, data: &[u8]) {
+    use std::arch::x86_64::*;
+
+    let clmul = |x: u64, y: u64| {
+        let x = _mm_set_epi64x(0, x as i64);
+        let y = _mm_set_epi64x(0, y as i64);
+        std::mem::transmute::<__m128i, u128>(_mm_clmulepi64_si128(x, y, 0))
+    };
+    blocks_with(s, h, data, clmul);
+}
+
+// s = (s + x) * h * x^-128, for each 16-byte block x of `data`
+fn blocks(s: &mut u128, h: u128, data: &[u8]) {
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("pclmulqdq") {
+        // SAFETY: we just checked that the CPU supports PCLMULQDQ
+        unsafe { blocks_pclmul(s, h, data) };
+        return;
+    }
+
+    blocks_with(s, h, data, clmul_soft);
+}
+
+/// POLYVAL, keyed with a 16-byte field element.
+#[derive(Clone)]
+pub struct Polyval {
+    h: u128,
+    s: u128,
+}
+
+impl Polyval {
+    pub fn new(key: &[u8; 16]) -> Self {
+        Self::from_element(u128::from_le_bytes(*key))
+    }
+
+    pub(crate) fn from_element(h: u128) -> Self {
+        Self { h, s: 0 }
+    }
+
+    /// Absorbs `data`, padded with zeros to a multiple of 16 bytes.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        let full = data.len() - data.len() % 16;
+        blocks(&mut self.s, self.h, &data[..full]);
+
+        let rest = &data[full..];
+        if !rest.is_empty() {
+            let mut block = [0; 16];
+            block[..rest.len()].copy_from_slice(rest);
+            blocks(&mut self.s, self.h, &block);
+        }
+    }
+
+    pub(crate) fn element(&self) -> u128 {
+        self.s
+    }
+
+    pub fn finalize(self) -> [u8; 16] {
+        self.s.to_le_bytes()
+    }
+}
+
+impl UniversalHash for Polyval {
+    type Key = [u8; 16];
+
+    fn new(key: &[u8; 16]) -> Self {
+        Self::new(key)
+    }
+
+    fn update_padded(&mut self, data: &[u8]) {
+        self.update_padded(data);
+    }
+
+    fn finalize(self) -> [u8; 16] {
+        self.finalize()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn pclmul_matches_soft() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(128);
+    for _ in 0..1000 {
+        let (a, b): (u128, u128) = (rng.gen(), rng.gen());
+        let soft = dot(a, b, clmul_soft);
+
+        #[cfg(target_arch = "x86_64")]
+        if std::is_x86_feature_detected!("pclmulqdq") {
+            let mut s = a;
+            unsafe { blocks_pclmul(&mut s, b, &[0; 16]) };
+            assert_eq!(s, soft);
+        }
+
+        // x^128 mod P is the identity of `dot`
+        assert_eq!(dot(a, X128, clmul_soft), a);
+        assert_eq!(dot(a, b, clmul_soft), dot(b, a, clmul_soft));
+    }
+}
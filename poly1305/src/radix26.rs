@@ -0,0 +1,245 @@
+//! Poly1305 arithmetic with five 26-bit limbs
+//!
+//! The accumulator `h` and the key `r` are split in 26-bit limbs held in `u32`s,
+//! so that the products of the multiplication fit in `u64`s with room for the sums.
+//! Limbs above 2^130 wrap around multiplied by 5, since 2^130 = 5 mod p.
+//! There are no data-dependent branches nor memory accesses.
+
+#[cfg(target_arch = "x86_64")]
+use crate::avx2;
+use crate::lanes;
+
+pub(crate) const MASK: u32 = (1 << 26) - 1;
+
+// below this length, computing the powers of r costs more than it saves
+const MULTIBLOCK_MIN: usize = 256;
+
+fn le32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().unwrap())
+}
+
+// the 16-byte `block` split in 26-bit limbs, with `hibit` added to the top one
+#[inline(always)]
+pub(crate) fn limbs(block: &[u8], hibit: u32) -> [u32; 5] {
+    [
+        le32(&block[0..]) & MASK,
+        (le32(&block[3..]) >> 2) & MASK,
+        (le32(&block[6..]) >> 4) & MASK,
+        (le32(&block[9..]) >> 6) & MASK,
+        (le32(&block[12..]) >> 8) | hibit,
+    ]
+}
+
+/// Implementation of the loop over long messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// One block at a time
+    OneBlock,
+    /// Four blocks at a time, without SIMD intrinsics
+    Lanes,
+    /// Four blocks at a time with AVX2
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+}
+
+impl Backend {
+    /// The fastest implementation on this CPU.
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        if std::is_x86_feature_detected!("avx2") {
+            return Backend::Avx2;
+        }
+        Backend::Lanes
+    }
+}
+
+// 5 * r[1..], for the limbs that wrap around
+pub(crate) fn times5(r: &[u32; 5]) -> [u32; 4] {
+    [r[1] * 5, r[2] * 5, r[3] * 5, r[4] * 5]
+}
+
+// h * r, with `s` = times5(r). The result is only partially reduced,
+// with limbs of 26 bits but for h[1] which can be just above.
+#[inline(always)]
+pub(crate) fn mul(h: [u32; 5], r: &[u32; 5], s: &[u32; 4]) -> [u32; 5] {
+    let [h0, h1, h2, h3, h4] = h.map(u64::from);
+    let [r0, r1, r2, r3, r4] = r.map(u64::from);
+    let [s1, s2, s3, s4] = s.map(u64::from);
+
+    let d0 = h0 * r0 + h1 * s4 + h2 * s3 + h3 * s2 + h4 * s1;
+    let mut d1 = h0 * r1 + h1 * r0 + h2 * s4 + h3 * s3 + h4 * s2;
+    let mut d2 = h0 * r2 + h1 * r1 + h2 * r0 + h3 * s4 + h4 * s3;
+    let mut d3 = h0 * r3 + h1 * r2 + h2 * r1 + h3 * r0 + h4 * s4;
+    let mut d4 = h0 * r4 + h1 * r3 + h2 * r2 + h3 * r1 + h4 * r0;
+
+    d1 += d0 >> 26;
+    d2 += d1 >> 26;
+    d3 += d2 >> 26;
+    d4 += d3 >> 26;
+    let h0 = (d0 as u32 & MASK) + (d4 >> 26) as u32 * 5;
+
+    [
+        h0 & MASK,
+        (d1 as u32 & MASK) + (h0 >> 26),
+        d2 as u32 & MASK,
+        d3 as u32 & MASK,
+        d4 as u32 & MASK,
+    ]
+}
+
+#[derive(Clone)]
+pub struct State {
+    r: [u32; 5],
+    s: [u32; 4],
+    h: [u32; 5],
+    pad: u128,
+    backend: Backend,
+    // computed on the first long enough input
+    powers: Option<lanes::Powers>,
+    #[cfg(target_arch = "x86_64")]
+    avx2_powers: Option<avx2::Powers>,
+}
+
+impl State {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self::with_backend(key, Backend::detect())
+    }
+
+    /// # Panics
+    /// If `backend` is AVX2 and the CPU does not support it.
+    pub fn with_backend(key: &[u8; 32], backend: Backend) -> Self {
+        #[cfg(target_arch = "x86_64")]
+        assert!(
+            backend != Backend::Avx2 || std::is_x86_feature_detected!("avx2"),
+            "AVX2 is not supported by this CPU"
+        );
+
+        // clamping is folded in the limb masks
+        let r = [
+            le32(&key[0..]) & 0x3ffffff,
+            (le32(&key[3..]) >> 2) & 0x3ffff03,
+            (le32(&key[6..]) >> 4) & 0x3ffc0ff,
+            (le32(&key[9..]) >> 6) & 0x3f03fff,
+            (le32(&key[12..]) >> 8) & 0x00fffff,
+        ];
+
+        Self {
+            r,
+            s: times5(&r),
+            h: [0; 5],
+            pad: u128::from_le_bytes(key[16..].try_into().unwrap()),
+            backend,
+            powers: None,
+            #[cfg(target_arch = "x86_64")]
+            avx2_powers: None,
+        }
+    }
+
+    // h = (h + m) * r, for each 16-byte block m of `data`.
+    // `hibit` is the 2^128 bit appended to full blocks
+    fn process(&mut self, data: &[u8], hibit: u32) {
+        let [mut h0, mut h1, mut h2, mut h3, mut h4] = self.h;
+
+        for block in data.chunks_exact(16) {
+            let [m0, m1, m2, m3, m4] = limbs(block, hibit);
+            h0 += m0;
+            h1 += m1;
+            h2 += m2;
+            h3 += m3;
+            h4 += m4;
+
+            [h0, h1, h2, h3, h4] = mul([h0, h1, h2, h3, h4], &self.r, &self.s);
+        }
+
+        self.h = [h0, h1, h2, h3, h4];
+    }
+
+    /// Processes whole 16-byte blocks. `data.len()` must be a multiple of 16.
+    pub fn blocks(&mut self, mut data: &[u8]) {
+        debug_assert!(data.len().is_multiple_of(16));
+
+        // long messages four blocks at a time, then the rest one by one
+        if data.len() >= MULTIBLOCK_MIN {
+            let (groups, rest) = data.split_at(data.len() - data.len() % 64);
+            match self.backend {
+                Backend::OneBlock => self.process(groups, 1 << 24),
+                Backend::Lanes => {
+                    let powers = self
+                        .powers
+                        .get_or_insert_with(|| lanes::Powers::new(&self.r));
+                    lanes::blocks(&mut self.h, powers, groups);
+                }
+                #[cfg(target_arch = "x86_64")]
+                Backend::Avx2 => {
+                    let powers = self
+                        .avx2_powers
+                        .get_or_insert_with(|| avx2::Powers::new(&self.r));
+                    // SAFETY: `with_backend` checked that the CPU supports AVX2
+                    unsafe { avx2::blocks(&mut self.h, powers, groups) };
+                }
+            }
+            data = rest;
+        }
+
+        self.process(data, 1 << 24);
+    }
+
+    /// Processes the last, shorter than 16 bytes, block of the message.
+    pub fn last_block(&mut self, data: &[u8]) {
+        debug_assert!(data.len() < 16);
+        if data.is_empty() {
+            return;
+        }
+
+        // the 2^(8 * len) bit is a 1 byte after the message
+        let mut block = [0; 16];
+        block[..data.len()].copy_from_slice(data);
+        block[data.len()] = 1;
+        self.process(&block, 0);
+    }
+
+    /// Fully reduces the accumulator modulo p, and adds the pad.
+    pub fn finalize(&self) -> u128 {
+        let [mut h0, mut h1, mut h2, mut h3, mut h4] = self.h;
+
+        // full carry propagation
+        h2 += h1 >> 26;
+        h1 &= MASK;
+        h3 += h2 >> 26;
+        h2 &= MASK;
+        h4 += h3 >> 26;
+        h3 &= MASK;
+        h0 += (h4 >> 26) * 5;
+        h4 &= MASK;
+        h1 += h0 >> 26;
+        h0 &= MASK;
+
+        // g = h + 5 - 2^130, which is positive iff h >= p
+        let mut g0 = h0.wrapping_add(5);
+        let mut g1 = h1.wrapping_add(g0 >> 26);
+        g0 &= MASK;
+        let mut g2 = h2.wrapping_add(g1 >> 26);
+        g1 &= MASK;
+        let mut g3 = h3.wrapping_add(g2 >> 26);
+        g2 &= MASK;
+        let g4 = h4.wrapping_add(g3 >> 26).wrapping_sub(1 << 26);
+        g3 &= MASK;
+
+        // select h if g is negative, g otherwise
+        let mask = (g4 >> 31).wrapping_sub(1);
+        let select = |h: u32, g: u32| (h & !mask) | (g & mask);
+        let h = [
+            select(h0, g0),
+            select(h1, g1),
+            select(h2, g2),
+            select(h3, g3),
+            select(h4, g4),
+        ];
+
+        // h mod 2^128, plus the pad
+        let h = h.iter().enumerate().fold(0u128, |acc, (i, &limb)| {
+            acc.wrapping_add((limb as u128) << (26 * i))
+        });
+        h.wrapping_add(self.pad)
+    }
+}
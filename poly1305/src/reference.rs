@@ -0,0 +1,44 @@
+//! Reference Poly1305, over generic big-integer residues
+//!
+//! Much slower than [`poly1305`](crate::poly1305), but close to the
+//! definition of RFC 8439. It is kept to cross-check the limb-based implementation.
+
+use crypto_bigint::{impl_modulus, modular::constant_mod::Residue, Encoding, U192};
+use io_utils::ReadExt;
+use std::io::{self, Read};
+
+use crate::clamp;
+
+impl_modulus!(
+    P1305,
+    U192,
+    "0000000000000003fffffffffffffffffffffffffffffffb"
+);
+
+type Fp = Residue<P1305, 3>;
+
+pub fn poly1305_reference(mut data: impl Read, key: &[u8; 32]) -> io::Result<u128> {
+    let r = u128::from_le_bytes(key[..16].try_into().unwrap());
+    let s = u128::from_le_bytes(key[16..].try_into().unwrap());
+
+    let r = clamp(r);
+    let r = Fp::new(&U192::from(r));
+
+    let mut acc = Fp::ZERO;
+    let mut buf;
+
+    loop {
+        buf = [0; 24];
+        let x = data.read_all(&mut buf[..16])?;
+        if x == 0 {
+            break;
+        }
+        buf[x] = 1;
+        let n = Fp::new(&U192::from_le_bytes(buf));
+        acc = (acc + n) * r;
+    }
+
+    // s is added modulo 2^128, not modulo p
+    let bytes = acc.retrieve().to_le_bytes()[..16].try_into().unwrap();
+    Ok(u128::from_le_bytes(bytes).wrapping_add(s))
+}
@@ -0,0 +1,157 @@
+use std::io::{self, Read};
+
+use io_utils::hex;
+use poly1305::{poly1305, poly1305_reference, Backend, Poly1305};
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+
+// a reader returning its data in small pieces
+struct Chunked<'a>(&'a [u8], usize);
+
+impl Read for Chunked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.1).min(self.0.len());
+        buf[..n].copy_from_slice(&self.0[..n]);
+        self.0 = &self.0[n..];
+        Ok(n)
+    }
+}
+
+fn check(msg: &[u8], key: &[u8; 32]) {
+    let expected = poly1305_reference(msg, key).unwrap();
+    assert_eq!(poly1305(msg, key).unwrap(), expected, "len {}", msg.len());
+    assert_eq!(poly1305(Chunked(msg, 7), key).unwrap(), expected);
+}
+
+#[test]
+fn random_inputs() {
+    let mut rng = StdRng::seed_from_u64(1305);
+
+    for len in 0..600 {
+        let mut key = [0; 32];
+        let mut msg = vec![0; len];
+        rng.fill_bytes(&mut key);
+        rng.fill_bytes(&mut msg);
+        check(&msg, &key);
+    }
+
+    // across the internal buffer boundary
+    for _ in 0..20 {
+        let mut key = [0; 32];
+        let mut msg = vec![0; rng.gen_range(4000..9000)];
+        rng.fill_bytes(&mut key);
+        rng.fill_bytes(&mut msg);
+        check(&msg, &key);
+    }
+}
+
+#[test]
+fn incremental() {
+    let mut rng = StdRng::seed_from_u64(1305);
+
+    for len in 0..300 {
+        let mut key = [0; 32];
+        let mut msg = vec![0; len];
+        rng.fill_bytes(&mut key);
+        rng.fill_bytes(&mut msg);
+
+        // feed the message in pieces of random lengths, including empty ones
+        let mut mac = Poly1305::new(&key);
+        let mut rest = &msg[..];
+        while !rest.is_empty() {
+            let (piece, tail) = rest.split_at(rng.gen_range(0..=rest.len().min(40)));
+            mac.update(piece);
+            rest = tail;
+        }
+
+        let expected = poly1305_reference(&msg[..], &key).unwrap().to_le_bytes();
+        assert_eq!(mac.clone().finalize(), expected);
+        assert!(mac.verify(&expected).is_ok());
+    }
+}
+
+#[test]
+fn extreme_inputs() {
+    // all-ones keys and messages keep the limbs as large as they can be
+    for key in [[0; 32], [0xff; 32]] {
+        for byte in [0, 0xff] {
+            for len in [0, 1, 15, 16, 17, 64, 4095, 4096, 4097] {
+                check(&vec![byte; len], &key);
+            }
+        }
+    }
+}
+
+// every implementation of the multi-block loop, whichever the CPU would pick
+#[test]
+fn backends() {
+    let mut backends = vec![Backend::OneBlock, Backend::Lanes];
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        backends.push(Backend::Avx2);
+    }
+
+    let mut rng = StdRng::seed_from_u64(1305);
+    let mut inputs = Vec::new();
+    for len in (0..1200).step_by(7) {
+        let mut key = [0; 32];
+        let mut msg = vec![0; len];
+        rng.fill_bytes(&mut key);
+        rng.fill_bytes(&mut msg);
+        inputs.push((key, msg));
+    }
+    for key in [[0; 32], [0xff; 32]] {
+        inputs.push((key, vec![0xff; 4096]));
+    }
+
+    for (key, msg) in &inputs {
+        let expected = poly1305_reference(&msg[..], key).unwrap().to_le_bytes();
+        for &backend in &backends {
+            let mut mac = Poly1305::with_backend(key, backend);
+            mac.update(msg);
+            assert_eq!(mac.finalize(), expected, "{backend:?}, len {}", msg.len());
+        }
+    }
+}
+
+// edge cases of RFC 8439, appendix A.3
+#[test]
+fn rfc8439_edge_cases() {
+    let vectors: [(&[u8], [u8; 32], [u8; 16]); 6] = [
+        (
+            &hex!("ffffffffffffffffffffffffffffffff"),
+            hex!("0200000000000000000000000000000000000000000000000000000000000000"),
+            hex!("03000000000000000000000000000000"),
+        ),
+        (
+            &hex!("02000000000000000000000000000000"),
+            hex!("02000000000000000000000000000000ffffffffffffffffffffffffffffffff"),
+            hex!("03000000000000000000000000000000"),
+        ),
+        (
+            &hex!("fffffffffffffffffffffffffffffffff0ffffffffffffffffffffffffffffff11000000000000000000000000000000"),
+            hex!("0100000000000000000000000000000000000000000000000000000000000000"),
+            hex!("05000000000000000000000000000000"),
+        ),
+        (
+            &hex!("fffffffffffffffffffffffffffffffffbfefefefefefefefefefefefefefefe01010101010101010101010101010101"),
+            hex!("0100000000000000000000000000000000000000000000000000000000000000"),
+            hex!("00000000000000000000000000000000"),
+        ),
+        (
+            &hex!("fdffffffffffffffffffffffffffffff"),
+            hex!("0200000000000000000000000000000000000000000000000000000000000000"),
+            hex!("faffffffffffffffffffffffffffffff"),
+        ),
+        (
+            &hex!("e33594d7505e43b900000000000000003394d7505e4379cd01000000000000000000000000000000000000000000000001000000000000000000000000000000"),
+            hex!("0100000000000000040000000000000000000000000000000000000000000000"),
+            hex!("14000000000000005500000000000000"),
+        ),
+    ];
+
+    for (msg, key, tag) in vectors {
+        let tag = u128::from_le_bytes(tag);
+        assert_eq!(poly1305(msg, &key).unwrap(), tag);
+        assert_eq!(poly1305_reference(msg, &key).unwrap(), tag);
+    }
+}
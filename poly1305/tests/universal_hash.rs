@@ -0,0 +1,119 @@
+use io_utils::hex;
+use poly1305::{poly1305_reference, Ghash, MacError, Poly1305, Polyval, UniversalHash};
+
+fn tag<H: UniversalHash>(key: &H::Key, pieces: &[&[u8]]) -> [u8; 16] {
+    let mut hash = H::new(key);
+    for piece in pieces {
+        hash.update_padded(piece);
+    }
+    hash.finalize()
+}
+
+fn message(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + 3) as u8).collect()
+}
+
+// test case 2 of the GCM specification
+#[test]
+fn ghash_gcm() {
+    let h = hex!("66e94bd4ef8a2c3b884cfa59ca342b2e");
+    let ciphertext = hex!("0388dace60b6a392f328c2b971b2fe78");
+    let lengths = hex!("00000000000000000000000000000080");
+
+    let expected = hex!("f38cbb1ad69223dcc3457ae5b6b0f885");
+    assert_eq!(tag::<Ghash>(&h, &[&ciphertext, &lengths]), expected);
+}
+
+// RFC 8452, appendix A
+#[test]
+fn polyval_rfc8452() {
+    let h = hex!("25629347589242761d31f826ba4b757b");
+    let x1 = hex!("4f4f95668c83dfb6401762bb2d01a262");
+    let x2 = hex!("d1a24ddd2721d006bbe45f20d3c9f362");
+
+    let expected = hex!("f7a3b47b846119fae5b7866cf5e5b77e");
+    assert_eq!(tag::<Polyval>(&h, &[&x1, &x2]), expected);
+    assert_eq!(tag::<Polyval>(&h, &[&[x1, x2].concat()]), expected);
+}
+
+// computed with a bitwise implementation of both specifications
+#[test]
+fn padded_messages() {
+    let h = hex!("25629347589242761d31f826ba4b757b");
+    let vectors: [(usize, [u8; 16], [u8; 16]); 7] = [
+        (0, [0; 16], [0; 16]),
+        (
+            1,
+            hex!("116b4f6b93d36d8d344ea410d79dbb3f"),
+            hex!("8f77534f4b0f20fedb7e985823fa901e"),
+        ),
+        (
+            15,
+            hex!("897c5a2f73e22560d5494b70d4da8d98"),
+            hex!("9a63a7785fb67df202c1a832ad5146a7"),
+        ),
+        (
+            16,
+            hex!("1f797eb5e1a795a6c60a8cd3de75ccc8"),
+            hex!("ce0193c2595e4e9f2aea65c647d57bd1"),
+        ),
+        (
+            17,
+            hex!("6f637cdcbd57996749d2f78ca387a0df"),
+            hex!("0a5374b44f5d84e5a50d047c8ddb7709"),
+        ),
+        (
+            100,
+            hex!("418efd61d12ca7d97689c1085b6783f7"),
+            hex!("f01938b9538e015293d90e3be2271db0"),
+        ),
+        (
+            1000,
+            hex!("98cbe0ab702711d305e36384e1dedbf5"),
+            hex!("72818a10fdd19710879b74d6b860f88e"),
+        ),
+    ];
+
+    for (len, ghash, polyval) in vectors {
+        let msg = message(len);
+        assert_eq!(tag::<Ghash>(&h, &[&msg]), ghash, "GHASH of {len} bytes");
+        assert_eq!(
+            tag::<Polyval>(&h, &[&msg]),
+            polyval,
+            "POLYVAL of {len} bytes"
+        );
+    }
+}
+
+#[test]
+fn poly1305_padded() {
+    let key = hex!("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b");
+    let (a, b) = (message(20), message(33));
+
+    // the pieces padded with zeros, as in the ChaCha20-Poly1305 AEAD
+    let padded = [&a[..], &[0; 12], &b, &[0; 15]].concat();
+    let expected = poly1305_reference(&padded[..], &key).unwrap().to_le_bytes();
+
+    assert_eq!(tag::<Poly1305>(&key, &[&a, &b]), expected);
+
+    let mut mac = <Poly1305 as UniversalHash>::new(&key);
+    mac.update_padded(&a);
+    mac.update_padded(&b);
+    assert_eq!(UniversalHash::verify(mac.clone(), &expected), Ok(()));
+    assert_eq!(UniversalHash::verify(mac, &[0; 16]), Err(MacError));
+}
+
+#[test]
+fn verify() {
+    let h = hex!("25629347589242761d31f826ba4b757b");
+    let msg = message(50);
+    let expected = tag::<Ghash>(&h, &[&msg]);
+
+    let mut ghash = Ghash::new(&h);
+    ghash.update_padded(&msg);
+    assert_eq!(ghash.clone().verify(&expected), Ok(()));
+
+    let mut forged = expected;
+    forged[0] ^= 0x80;
+    assert_eq!(ghash.verify(&forged), Err(MacError));
+}
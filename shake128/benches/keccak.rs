@@ -0,0 +1,58 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use shake128::{keccak_p, keccak_p_reference, sha3_256, sha3_256_batch, shake128, AlignedData};
+
+fn permutation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("keccak-f[1600]");
+    group.throughput(Throughput::Bytes(200));
+
+    let mut data = AlignedData([0; 200]);
+    group.bench_function("optimized", |b| b.iter(|| keccak_p(black_box(&mut data))));
+    group.bench_function("reference", |b| {
+        b.iter(|| keccak_p_reference(black_box(&mut data)))
+    });
+
+    group.finish();
+}
+
+fn shake(c: &mut Criterion) {
+    const LEN: usize = 1 << 20;
+    let msg = vec![0xa5; LEN];
+
+    let mut group = c.benchmark_group("shake128");
+    group.throughput(Throughput::Bytes(LEN as u64));
+
+    group.bench_function("absorb 1MiB", |b| {
+        b.iter(|| shake128(black_box(msg.as_slice())).unwrap().next())
+    });
+    group.bench_function("squeeze 1MiB", |b| {
+        let mut out = vec![0; LEN];
+        b.iter(|| {
+            shake128(&b""[..]).unwrap().squeeze(black_box(&mut out));
+        })
+    });
+
+    group.finish();
+}
+
+fn batch(c: &mut Criterion) {
+    const COUNT: usize = 64;
+    let msgs = vec![[0xa5; 64]; COUNT];
+    let refs: Vec<&[u8]> = msgs.iter().map(|m| &m[..]).collect();
+
+    let mut group = c.benchmark_group("sha3-256 of 64 short messages");
+    group.throughput(Throughput::Elements(COUNT as u64));
+
+    group.bench_function("one by one", |b| {
+        b.iter(|| {
+            for msg in &refs {
+                black_box(sha3_256(black_box(*msg)).unwrap());
+            }
+        })
+    });
+    group.bench_function("batched", |b| b.iter(|| sha3_256_batch(black_box(&refs))));
+
+    group.finish();
+}
+
+criterion_group!(benches, permutation, shake, batch);
+criterion_main!(benches);
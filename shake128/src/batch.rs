@@ -0,0 +1,201 @@
+//! Multi-way Keccak-f\[1600\], hashing several independent messages at once
+//!
+//! The states are interleaved lane by lane: `lanes[i][k]` is lane `i` of state `k`.
+//! Each step of the permutation then applies the same operation to `N` contiguous
+//! words, which the compiler turns into SIMD instructions. On x86_64, a copy of
+//! the permutation compiled for AVX2 is selected at runtime when available.
+
+use crate::{KeccakFlavour, Rate, Sha3, Shake, RC, RHO_OFFSETS};
+
+#[inline(always)]
+#[allow(clippy::needless_range_loop)]
+fn round<const N: usize>(a: &mut [[u64; N]; 25], rc: u64) {
+    // theta
+    let mut c = [[0; N]; 5];
+    for x in 0..5 {
+        for k in 0..N {
+            c[x][k] = a[x][k] ^ a[x + 5][k] ^ a[x + 10][k] ^ a[x + 15][k] ^ a[x + 20][k];
+        }
+    }
+    for x in 0..5 {
+        for k in 0..N {
+            let d = c[(x + 4) % 5][k] ^ c[(x + 1) % 5][k].rotate_left(1);
+            for y in 0..5 {
+                a[5 * y + x][k] ^= d;
+            }
+        }
+    }
+
+    // rho and pi
+    let mut b = [[0; N]; 25];
+    for y in 0..5 {
+        for x in 0..5 {
+            let (sx, sy) = ((x + 3 * y) % 5, x);
+            for k in 0..N {
+                b[5 * y + x][k] = a[5 * sy + sx][k].rotate_left(RHO_OFFSETS[sy][sx]);
+            }
+        }
+    }
+
+    // chi
+    for y in 0..5 {
+        for x in 0..5 {
+            for k in 0..N {
+                a[5 * y + x][k] =
+                    b[5 * y + x][k] ^ (!b[5 * y + (x + 1) % 5][k] & b[5 * y + (x + 2) % 5][k]);
+            }
+        }
+    }
+
+    // iota
+    a[0].iter_mut().for_each(|lane| *lane ^= rc);
+}
+
+#[inline(always)]
+fn keccak_p_portable<const N: usize>(lanes: &mut [[u64; N]; 25], rounds: usize) {
+    for &rc in &RC[24 - rounds..] {
+        round(lanes, rc);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn keccak_p_avx2<const N: usize>(lanes: &mut [[u64; N]; 25], rounds: usize) {
+    keccak_p_portable(lanes, rounds);
+}
+
+/// Keccak-f\[1600\] applied to `N` interleaved states.
+pub fn keccak_p_batch<const N: usize>(lanes: &mut [[u64; N]; 25]) {
+    keccak_p_batch_rounds(lanes, 24);
+}
+
+/// Keccak-p\[1600, rounds\] applied to `N` interleaved states.
+pub fn keccak_p_batch_rounds<const N: usize>(lanes: &mut [[u64; N]; 25], rounds: usize) {
+    assert!(rounds <= 24, "Keccak-p[1600] has at most 24 rounds");
+
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: we just checked that the CPU supports AVX2
+        unsafe { keccak_p_avx2(lanes, rounds) };
+        return;
+    }
+
+    keccak_p_portable(lanes, rounds);
+}
+
+fn xor_bytes<const N: usize>(lanes: &mut [[u64; N]; 25], k: usize, offset: usize, bytes: &[u8]) {
+    for (i, byte) in bytes.iter().enumerate() {
+        let pos = offset + i;
+        lanes[pos / 8][k] ^= (*byte as u64) << (8 * (pos % 8));
+    }
+}
+
+// absorbs W messages in lock-step, then squeezes each output.
+// messages may have different lengths: the states that are done absorbing
+// are saved before each permutation and restored after it.
+fn sponge_batch<F: KeccakFlavour, const W: usize>(msgs: &[&[u8]], outputs: &mut [&mut [u8]]) {
+    assert!(msgs.len() == W && outputs.len() == W);
+
+    let mut lanes = [[0; W]; 25];
+
+    // the final block holds the end of the message and the padding
+    let blocks: [usize; W] = std::array::from_fn(|k| msgs[k].len() / F::R + 1);
+    let max_blocks = blocks.iter().copied().max().unwrap_or(0);
+
+    for block in 0..max_blocks {
+        for k in 0..W {
+            if block + 1 < blocks[k] {
+                xor_bytes(&mut lanes, k, 0, &msgs[k][block * F::R..(block + 1) * F::R]);
+            } else if block + 1 == blocks[k] {
+                let rest = &msgs[k][block * F::R..];
+                xor_bytes(&mut lanes, k, 0, rest);
+                xor_bytes(&mut lanes, k, rest.len(), &[F::SUFFIX.domain_byte()]);
+                xor_bytes(&mut lanes, k, F::R - 1, &[0b1000_0000]);
+            }
+        }
+
+        let saved = lanes;
+        keccak_p_batch_rounds(&mut lanes, F::ROUNDS);
+        for k in (0..W).filter(|&k| block >= blocks[k]) {
+            lanes.iter_mut().zip(&saved).for_each(|(l, s)| l[k] = s[k]);
+        }
+    }
+
+    // squeezing
+    let max_len = outputs.iter().map(|o| o.len()).max().unwrap_or(0);
+    let mut pos = 0;
+    while pos < max_len {
+        for (k, output) in outputs.iter_mut().enumerate() {
+            for i in pos..output.len().min(pos + F::R) {
+                output[i] = (lanes[(i - pos) / 8][k] >> (8 * ((i - pos) % 8))) as u8;
+            }
+        }
+
+        pos += F::R;
+        if pos < max_len {
+            keccak_p_batch_rounds(&mut lanes, F::ROUNDS);
+        }
+    }
+}
+
+/// Hashes each message of `msgs` into the matching buffer of `outputs`,
+/// using the widest available batch for as many messages as possible.
+pub fn keccak_batch<F: KeccakFlavour>(msgs: &[&[u8]], outputs: &mut [&mut [u8]]) {
+    assert_eq!(msgs.len(), outputs.len(), "one output per message");
+
+    let mut msgs = msgs;
+    let mut outputs = outputs;
+
+    while !msgs.is_empty() {
+        let width = match msgs.len() {
+            8.. => 8,
+            4..=7 => 4,
+            2..=3 => 2,
+            _ => 1,
+        };
+
+        let (m, rest_m) = msgs.split_at(width);
+        let (o, rest_o) = outputs.split_at_mut(width);
+        match width {
+            8 => sponge_batch::<F, 8>(m, o),
+            4 => sponge_batch::<F, 4>(m, o),
+            2 => sponge_batch::<F, 2>(m, o),
+            _ => sponge_batch::<F, 1>(m, o),
+        }
+
+        msgs = rest_m;
+        outputs = rest_o;
+    }
+}
+
+/// SHAKE128 of each message of `msgs`, with outputs of `output_len` bytes.
+pub fn shake128_batch(msgs: &[&[u8]], output_len: usize) -> Vec<Vec<u8>> {
+    let mut outputs = vec![vec![0; output_len]; msgs.len()];
+    let mut refs: Vec<&mut [u8]> = outputs.iter_mut().map(Vec::as_mut_slice).collect();
+    keccak_batch::<Shake<128>>(msgs, &mut refs);
+    outputs
+}
+
+/// SHAKE256 of each message of `msgs`, with outputs of `output_len` bytes.
+pub fn shake256_batch(msgs: &[&[u8]], output_len: usize) -> Vec<Vec<u8>> {
+    let mut outputs = vec![vec![0; output_len]; msgs.len()];
+    let mut refs: Vec<&mut [u8]> = outputs.iter_mut().map(Vec::as_mut_slice).collect();
+    keccak_batch::<Shake<256>>(msgs, &mut refs);
+    outputs
+}
+
+/// SHA3-256 of each message of `msgs`.
+pub fn sha3_256_batch(msgs: &[&[u8]]) -> Vec<[u8; 32]> {
+    let mut outputs = vec![[0; 32]; msgs.len()];
+    let mut refs: Vec<&mut [u8]> = outputs.iter_mut().map(|o| &mut o[..]).collect();
+    keccak_batch::<Sha3<256>>(msgs, &mut refs);
+    outputs
+}
+
+/// SHA3-512 of each message of `msgs`.
+pub fn sha3_512_batch(msgs: &[&[u8]]) -> Vec<[u8; 64]> {
+    let mut outputs = vec![[0; 64]; msgs.len()];
+    let mut refs: Vec<&mut [u8]> = outputs.iter_mut().map(|o| &mut o[..]).collect();
+    keccak_batch::<Sha3<512>>(msgs, &mut refs);
+    outputs
+}
@@ -0,0 +1,79 @@
+//! cSHAKE customizable extendable output functions, as defined in NIST SP 800-185
+
+use std::io::{self, Read};
+
+use crate::{Rate, Shake, Shake128Reader, Shake256Reader, Sponge, SpongeReader, Suffix};
+
+/// Encodes `x` as its big endian byte string, prefixed by the length of that string.
+pub fn left_encode(x: u64) -> Vec<u8> {
+    // at least one byte must be encoded, even for x = 0
+    let n = (8 - x.leading_zeros() as usize / 8).max(1);
+
+    let mut res = Vec::with_capacity(n + 1);
+    res.push(n as u8);
+    res.extend_from_slice(&x.to_be_bytes()[8 - n..]);
+    res
+}
+
+/// Encodes `x` as its big endian byte string, followed by the length of that string.
+pub fn right_encode(x: u64) -> Vec<u8> {
+    let n = (8 - x.leading_zeros() as usize / 8).max(1);
+
+    let mut res = Vec::with_capacity(n + 1);
+    res.extend_from_slice(&x.to_be_bytes()[8 - n..]);
+    res.push(n as u8);
+    res
+}
+
+/// Prefixes a byte string with its length in bits, so that it can be parsed unambiguously.
+pub fn encode_string(s: &[u8]) -> Vec<u8> {
+    let mut res = left_encode(8 * s.len() as u64);
+    res.extend_from_slice(s);
+    res
+}
+
+/// Prefixes `x` with the encoding of `w`, then pads it with zeros to a multiple of `w` bytes.
+pub fn bytepad(x: &[u8], w: usize) -> Vec<u8> {
+    assert!(w > 0, "bytepad width must be positive");
+
+    let mut res = left_encode(w as u64);
+    res.extend_from_slice(x);
+    res.resize(res.len().div_ceil(w) * w, 0);
+    res
+}
+
+pub(crate) fn cshake<const SIZE: usize>(
+    name: &[u8],
+    customization: &[u8],
+    mut msg: impl Read,
+) -> io::Result<SpongeReader<Shake<SIZE>>> {
+    let mut sponge = Sponge::<Shake<SIZE>>::new();
+
+    // cSHAKE with no function name and no customization string is plain SHAKE
+    let suffix = if name.is_empty() && customization.is_empty() {
+        Suffix::S1111
+    } else {
+        let mut strings = encode_string(name);
+        strings.extend_from_slice(&encode_string(customization));
+        sponge.update(&bytepad(&strings, Shake::<SIZE>::R));
+        Suffix::S00
+    };
+
+    io::copy(&mut msg, &mut sponge)?;
+    Ok(sponge.finalize_with(suffix))
+}
+
+/// cSHAKE128 with function name `name` and customization string `customization`.
+///
+/// `name` is reserved for functions defined by NIST; other users should leave it empty
+/// and only use `customization` for domain separation.
+pub fn cshake128(name: &[u8], customization: &[u8], msg: impl Read) -> io::Result<Shake128Reader> {
+    cshake::<128>(name, customization, msg)
+}
+
+/// cSHAKE256 with function name `name` and customization string `customization`.
+///
+/// See [`cshake128`].
+pub fn cshake256(name: &[u8], customization: &[u8], msg: impl Read) -> io::Result<Shake256Reader> {
+    cshake::<256>(name, customization, msg)
+}
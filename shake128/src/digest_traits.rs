@@ -0,0 +1,101 @@
+//! Implementations of the RustCrypto `digest` traits,
+//! so that our hashers can be used in generic code (HMAC, HKDF, ...)
+
+use digest::{
+    consts::{U104, U136, U144, U168, U28, U32, U48, U64, U72},
+    core_api::BlockSizeUser,
+    ExtendableOutput, ExtendableOutputReset, FixedOutput, FixedOutputReset, HashMarker, Output,
+    OutputSizeUser, Reset, Update, XofReader,
+};
+
+use crate::turbo_shake::TurboShake;
+use crate::{Keccak, KeccakFlavour, Sha3, Shake, Sponge, SpongeReader};
+
+impl<F: KeccakFlavour> Update for Sponge<F> {
+    fn update(&mut self, data: &[u8]) {
+        Sponge::update(self, data);
+    }
+}
+
+impl<F: KeccakFlavour> Reset for Sponge<F> {
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<F: KeccakFlavour> XofReader for SpongeReader<F> {
+    fn read(&mut self, buffer: &mut [u8]) {
+        self.squeeze(buffer);
+    }
+}
+
+// fixed size hash functions: the typenum sizes cannot be computed
+// from the const generic parameter, so each instance is spelled out
+macro_rules! fixed_output {
+    ($($flavour:ty => $block:ty, $output:ty;)*) => {$(
+        impl HashMarker for Sponge<$flavour> {}
+
+        impl BlockSizeUser for Sponge<$flavour> {
+            type BlockSize = $block;
+        }
+
+        impl OutputSizeUser for Sponge<$flavour> {
+            type OutputSize = $output;
+        }
+
+        impl FixedOutput for Sponge<$flavour> {
+            fn finalize_into(self, out: &mut Output<Self>) {
+                self.finalize_xof().squeeze(out);
+            }
+        }
+
+        impl FixedOutputReset for Sponge<$flavour> {
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                std::mem::take(self).finalize_xof().squeeze(out);
+            }
+        }
+    )*};
+}
+
+fixed_output! {
+    Sha3<224> => U144, U28;
+    Sha3<256> => U136, U32;
+    Sha3<384> => U104, U48;
+    Sha3<512> => U72, U64;
+    Keccak<224> => U144, U28;
+    Keccak<256> => U136, U32;
+    Keccak<384> => U104, U48;
+    Keccak<512> => U72, U64;
+}
+
+// extendable output functions
+macro_rules! extendable_output {
+    ($($flavour:ty => $block:ty;)*) => {$(
+        impl HashMarker for Sponge<$flavour> {}
+
+        impl BlockSizeUser for Sponge<$flavour> {
+            type BlockSize = $block;
+        }
+
+        impl ExtendableOutput for Sponge<$flavour> {
+            type Reader = SpongeReader<$flavour>;
+
+            fn finalize_xof(self) -> Self::Reader {
+                Sponge::finalize_xof(self)
+            }
+        }
+
+        impl ExtendableOutputReset for Sponge<$flavour> {
+            fn finalize_xof_reset(&mut self) -> Self::Reader {
+                std::mem::take(self).finalize_xof()
+            }
+        }
+    )*};
+}
+
+extendable_output! {
+    Shake<128> => U168;
+    Shake<256> => U136;
+    TurboShake<128> => U168;
+    TurboShake<256> => U136;
+}
@@ -0,0 +1,105 @@
+//! Deterministic random bit generator built on SHAKE256
+//!
+//! The generator is a SHAKE256 sponge squeezing its output. Seeding, reseeding
+//! and ratcheting all start a fresh sponge from an unambiguous encoding of
+//! their inputs, so no two of them can lead to the same state.
+//!
+//! The Keccak permutation is invertible: whoever learns the state can recover
+//! the previous outputs of the same sponge. [`ShakeDrbg::ratchet`] replaces the
+//! state with a hash of itself, after which earlier outputs cannot be recovered.
+
+use std::fmt;
+
+use rand_core::{impls, CryptoRng, RngCore, SeedableRng};
+
+use crate::cshake::encode_string;
+use crate::{Shake256Hasher, Shake256Reader};
+
+/// SHAKE256-based DRBG, implementing the `rand_core` traits.
+#[derive(Clone)]
+pub struct ShakeDrbg {
+    reader: Shake256Reader,
+}
+
+// the state is secret, do not print it
+impl fmt::Debug for ShakeDrbg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShakeDrbg").finish_non_exhaustive()
+    }
+}
+
+impl ShakeDrbg {
+    /// Creates a generator whose output is entirely determined by `seed`.
+    pub fn new(seed: &[u8]) -> Self {
+        Self::derive(b"seed", &[seed])
+    }
+
+    // a fresh sponge over the encoded operation label and inputs
+    fn derive(label: &[u8], inputs: &[&[u8]]) -> Self {
+        let mut sponge = Shake256Hasher::new();
+        sponge.update(&encode_string(b"ShakeDrbg"));
+        sponge.update(&encode_string(label));
+        for input in inputs {
+            sponge.update(&encode_string(input));
+        }
+
+        Self {
+            reader: sponge.finalize_xof(),
+        }
+    }
+
+    // the part of the current state carried over to the next one
+    fn chaining_key(&mut self) -> [u8; 64] {
+        let mut key = [0; 64];
+        self.reader.squeeze(&mut key);
+        key
+    }
+
+    /// Mixes `entropy` into the state. The output then depends on both
+    /// the previous state and `entropy`.
+    pub fn reseed(&mut self, entropy: &[u8]) {
+        let key = self.chaining_key();
+        *self = Self::derive(b"reseed", &[&key, entropy]);
+    }
+
+    /// Replaces the state with a one-way function of itself, so that
+    /// a later compromise of the generator does not reveal the earlier outputs.
+    pub fn ratchet(&mut self) {
+        let key = self.chaining_key();
+        *self = Self::derive(b"ratchet", &[&key]);
+    }
+
+    /// Fills `out` with the next bytes of output.
+    pub fn fill(&mut self, out: &mut [u8]) {
+        self.reader.squeeze(out);
+    }
+}
+
+impl RngCore for ShakeDrbg {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.fill(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for ShakeDrbg {}
+
+impl SeedableRng for ShakeDrbg {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(&seed)
+    }
+}
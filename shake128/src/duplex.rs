@@ -0,0 +1,78 @@
+//! Duplex construction over Keccak-f\[1600\]
+//!
+//! Unlike a [`Sponge`](crate::Sponge), a duplex object alternates absorbing and
+//! squeezing: each call pads and absorbs an input block, permutes the state,
+//! and returns up to a full block of output that depends on every input so far.
+
+use std::marker::PhantomData;
+
+use crate::{keccak_p_rounds, AlignedData, KeccakFlavour, Rate, Shake, Suffix};
+
+/// Keccak duplex object with the capacity (and number of rounds) of flavour `F`.
+#[derive(Debug)]
+pub struct Duplex<F: KeccakFlavour> {
+    data: AlignedData,
+    flavour: PhantomData<F>,
+}
+
+impl<F: KeccakFlavour> Clone for Duplex<F> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            flavour: PhantomData,
+        }
+    }
+}
+
+impl<F: KeccakFlavour> Default for Duplex<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: KeccakFlavour> Duplex<F> {
+    /// Size of the outputs, in bytes. Inputs can be at most one byte shorter,
+    /// to leave room for the padding.
+    pub const RATE: usize = F::R;
+
+    pub fn new() -> Self {
+        Self {
+            data: AlignedData([0; 200]),
+            flavour: PhantomData,
+        }
+    }
+
+    /// Absorbs the padded `input` and returns the next [`RATE`](Self::RATE) bytes
+    /// of output. Shorter outputs are prefixes of this one.
+    ///
+    /// # Panics
+    /// If `input` is longer than `RATE - 1` bytes.
+    pub fn duplex(&mut self, input: &[u8]) -> &[u8] {
+        self.duplex_with(input, Suffix::NONE)
+    }
+
+    // `suffix` bits are appended to the input before padding,
+    // as the frame bits of SpongeWrap
+    pub(crate) fn duplex_with(&mut self, input: &[u8], suffix: Suffix) -> &[u8] {
+        assert!(
+            input.len() < F::R,
+            "duplex inputs are at most {} bytes",
+            F::R - 1
+        );
+
+        self.data[..input.len()]
+            .iter_mut()
+            .zip(input)
+            .for_each(|(s, i)| *s ^= i);
+        self.data[input.len()] ^= suffix.domain_byte();
+        self.data[F::R - 1] ^= 0b1000_0000;
+        keccak_p_rounds(&mut self.data, F::ROUNDS);
+
+        &self.data[..F::R]
+    }
+}
+
+/// Duplex object with a capacity of 256 bits, as in SHAKE128.
+pub type Duplex128 = Duplex<Shake<128>>;
+/// Duplex object with a capacity of 512 bits, as in SHAKE256.
+pub type Duplex256 = Duplex<Shake<256>>;
@@ -0,0 +1,81 @@
+//! KangarooTwelve (KT128 and KT256), as defined in RFC 9861
+//!
+//! Inputs longer than a single chunk are hashed as a tree: every chunk after
+//! the first one is compressed to a chaining value, and the chaining values
+//! are appended to the first chunk to form the final node.
+
+use std::io::{self, Cursor, Read};
+
+use io_utils::ReadExt;
+
+use crate::turbo_shake::{turbo_shake, TurboShake};
+use crate::{Sponge, SpongeReader, TurboShake128Reader, TurboShake256Reader};
+
+const CHUNK_SIZE: usize = 8192;
+
+// domain separation bytes of the different node types
+const SINGLE_NODE: u8 = 0x07;
+const FINAL_NODE: u8 = 0x06;
+const LEAF_NODE: u8 = 0x0b;
+
+/// Encodes `x` as its big endian byte string without leading zeros,
+/// followed by the length of that string.
+///
+/// Unlike [`right_encode`](crate::cshake::right_encode), 0 is encoded as an empty string.
+pub fn length_encode(x: u64) -> Vec<u8> {
+    let n = 8 - x.leading_zeros() as usize / 8;
+
+    let mut res = Vec::with_capacity(n + 1);
+    res.extend_from_slice(&x.to_be_bytes()[8 - n..]);
+    res.push(n as u8);
+    res
+}
+
+fn kangaroo_twelve<const SIZE: usize>(
+    msg: impl Read,
+    customization: &[u8],
+) -> io::Result<SpongeReader<TurboShake<SIZE>>> {
+    let mut suffix = customization.to_vec();
+    suffix.extend_from_slice(&length_encode(customization.len() as u64));
+    let mut s = msg.chain(Cursor::new(suffix));
+
+    // read one byte past the first chunk, to know if there is more than one
+    let mut chunk = vec![0; CHUNK_SIZE + 1];
+    let n = s.read_all(&mut chunk)?;
+    if n <= CHUNK_SIZE {
+        return turbo_shake::<SIZE>(&chunk[..n], SINGLE_NODE);
+    }
+
+    let mut final_node = Sponge::<TurboShake<SIZE>>::new();
+    final_node.update(&chunk[..CHUNK_SIZE]);
+    final_node.update(&[0x03, 0, 0, 0, 0, 0, 0, 0]);
+
+    // the extra byte we read starts the second chunk
+    chunk[0] = chunk[CHUNK_SIZE];
+    let mut n = 1 + s.read_all(&mut chunk[1..CHUNK_SIZE])?;
+    let mut leaves = 0;
+    let mut chaining_value = vec![0; SIZE / 4];
+
+    while n > 0 {
+        turbo_shake::<SIZE>(&chunk[..n], LEAF_NODE)?.squeeze(&mut chaining_value);
+        final_node.update(&chaining_value);
+        leaves += 1;
+
+        n = s.read_all(&mut chunk[..CHUNK_SIZE])?;
+    }
+
+    final_node.update(&length_encode(leaves));
+    final_node.update(&[0xff, 0xff]);
+
+    Ok(final_node.finalize_with_domain(FINAL_NODE))
+}
+
+/// KT128 of `msg` with the customization string `customization`.
+pub fn kt128(msg: impl Read, customization: &[u8]) -> io::Result<TurboShake128Reader> {
+    kangaroo_twelve::<128>(msg, customization)
+}
+
+/// KT256 of `msg` with the customization string `customization`.
+pub fn kt256(msg: impl Read, customization: &[u8]) -> io::Result<TurboShake256Reader> {
+    kangaroo_twelve::<256>(msg, customization)
+}
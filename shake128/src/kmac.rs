@@ -0,0 +1,110 @@
+//! KMAC keyed message authentication codes, as defined in NIST SP 800-185
+
+use std::io::{self, Cursor, Read};
+
+use io_utils::ct_eq;
+
+use crate::cshake::{bytepad, cshake, encode_string, right_encode};
+use crate::{Rate, Shake, Shake128Reader, Shake256Reader, SpongeReader};
+
+// KMAC is cSHAKE over the padded key, the message, then the encoded output length
+fn kmac_xof<const SIZE: usize>(
+    key: &[u8],
+    msg: impl Read,
+    output_bits: u64,
+    customization: &[u8],
+) -> io::Result<SpongeReader<Shake<SIZE>>> {
+    let key = bytepad(&encode_string(key), Shake::<SIZE>::R);
+    let len = right_encode(output_bits);
+
+    cshake::<SIZE>(
+        b"KMAC",
+        customization,
+        Cursor::new(key).chain(msg).chain(Cursor::new(len)),
+    )
+}
+
+fn kmac<const SIZE: usize>(
+    key: &[u8],
+    msg: impl Read,
+    len: usize,
+    customization: &[u8],
+) -> io::Result<Vec<u8>> {
+    kmac_xof::<SIZE>(key, msg, 8 * len as u64, customization).map(|mac| mac.take(len).collect())
+}
+
+/// KMAC128 of `msg` under `key`, with an output of `len` bytes.
+///
+/// The output length is part of the computation: a shorter tag is not
+/// a prefix of a longer one.
+pub fn kmac128(
+    key: &[u8],
+    msg: impl Read,
+    len: usize,
+    customization: &[u8],
+) -> io::Result<Vec<u8>> {
+    kmac::<128>(key, msg, len, customization)
+}
+
+/// KMAC256 of `msg` under `key`, with an output of `len` bytes.
+pub fn kmac256(
+    key: &[u8],
+    msg: impl Read,
+    len: usize,
+    customization: &[u8],
+) -> io::Result<Vec<u8>> {
+    kmac::<256>(key, msg, len, customization)
+}
+
+/// KMACXOF128 of `msg` under `key`, with an arbitrary output length.
+pub fn kmac_xof128(key: &[u8], msg: impl Read, customization: &[u8]) -> io::Result<Shake128Reader> {
+    kmac_xof::<128>(key, msg, 0, customization)
+}
+
+/// KMACXOF256 of `msg` under `key`, with an arbitrary output length.
+pub fn kmac_xof256(key: &[u8], msg: impl Read, customization: &[u8]) -> io::Result<Shake256Reader> {
+    kmac_xof::<256>(key, msg, 0, customization)
+}
+
+// the tag must have the length expected by the verifier: taking it from the
+// tag would let a sender choose a short, easily forged one
+fn verify<const SIZE: usize>(
+    key: &[u8],
+    msg: impl Read,
+    customization: &[u8],
+    len: usize,
+    tag: &[u8],
+) -> io::Result<bool> {
+    if len == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "KMAC tags cannot be empty",
+        ));
+    }
+    if tag.len() != len {
+        return Ok(false);
+    }
+    kmac::<SIZE>(key, msg, len, customization).map(|mac| ct_eq(&mac, tag))
+}
+
+/// Checks in constant time a KMAC128 tag of `len` bytes. Tags of any other length are rejected.
+pub fn verify128(
+    key: &[u8],
+    msg: impl Read,
+    customization: &[u8],
+    len: usize,
+    tag: &[u8],
+) -> io::Result<bool> {
+    verify::<128>(key, msg, customization, len, tag)
+}
+
+/// Checks in constant time a KMAC256 tag of `len` bytes. Tags of any other length are rejected.
+pub fn verify256(
+    key: &[u8],
+    msg: impl Read,
+    customization: &[u8],
+    len: usize,
+    tag: &[u8],
+) -> io::Result<bool> {
+    verify::<256>(key, msg, customization, len, tag)
+}
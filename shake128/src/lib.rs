@@ -1,10 +1,40 @@
 use std::{
+    fmt::Debug,
     io::{self, Read},
-    ops::{Deref, DerefMut},
+    ops::{BitAnd, BitXor, BitXorAssign, Deref, DerefMut, Not},
 };
 
 use bytemuck::{Pod, Zeroable};
-use io_utils::ReadExt;
+
+pub mod batch;
+pub mod cshake;
+mod digest_traits;
+pub mod drbg;
+pub mod duplex;
+pub mod kangaroo_twelve;
+pub mod kmac;
+pub mod optimized;
+pub mod parallel_hash;
+pub mod sponge;
+pub mod sponge_wrap;
+pub mod tuple_hash;
+pub mod turbo_shake;
+
+pub use batch::{sha3_256_batch, sha3_512_batch, shake128_batch, shake256_batch};
+pub use cshake::{cshake128, cshake256};
+pub use drbg::ShakeDrbg;
+pub use duplex::{Duplex, Duplex128, Duplex256};
+pub use kangaroo_twelve::{kt128, kt256};
+pub use kmac::{kmac128, kmac256, kmac_xof128, kmac_xof256};
+pub use parallel_hash::{
+    parallel_hash128, parallel_hash256, parallel_hash_xof128, parallel_hash_xof256,
+};
+pub use sponge::{
+    SeekableReader, Sha3_224Hasher, Sha3_256Hasher, Sha3_384Hasher, Sha3_512Hasher, Shake128Hasher,
+    Shake128Reader, Shake256Hasher, Shake256Reader, Sponge, SpongeReader,
+};
+pub use tuple_hash::{tuple_hash128, tuple_hash256, tuple_hash_xof128, tuple_hash_xof256};
+pub use turbo_shake::{turbo_shake128, turbo_shake256, TurboShake128Reader, TurboShake256Reader};
 
 // ensure our 'data' array is correctly aligned
 // so that we can safely cast it to State
@@ -25,14 +55,61 @@ impl DerefMut for AlignedData {
     }
 }
 
-#[repr(C)]
-#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Pod, Zeroable)]
-pub struct State {
-    data: [[u64; 5]; 5],
+/// Lane of a Keccak-f\[b\] state, of `w = b / 25` bits.
+///
+/// `u64` gives Keccak-f\[1600\], and `u32`, `u16` and `u8` the smaller members
+/// of the family: Keccak-f\[800\], Keccak-f\[400\] and Keccak-f\[200\].
+/// Keccak-f\[25\], \[50\] and \[100\] have lanes narrower than a byte, and are not supported.
+pub trait Lane:
+    Pod
+    + Default
+    + Eq
+    + Debug
+    + BitXor<Output = Self>
+    + BitXorAssign
+    + BitAnd<Output = Self>
+    + Not<Output = Self>
+{
+    /// `w`, the lane width in bits
+    const BITS: u32;
+    /// `12 + 2ℓ`, where `w = 2^ℓ`
+    const ROUNDS: usize = 12 + 2 * Self::BITS.trailing_zeros() as usize;
+
+    fn rotate_left(self, n: u32) -> Self;
+    /// The low `w` bits of a 64-bit constant
+    fn truncate(x: u64) -> Self;
+}
+
+macro_rules! lane {
+    ($($t:ty),*) => {$(
+        impl Lane for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            fn rotate_left(self, n: u32) -> Self {
+                <$t>::rotate_left(self, n)
+            }
+
+            fn truncate(x: u64) -> Self {
+                x as $t
+            }
+        }
+    )*};
 }
 
-impl From<[[u64; 5]; 5]> for State {
-    fn from(value: [[u64; 5]; 5]) -> Self {
+lane!(u8, u16, u32, u64);
+
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct State<L: Lane = u64> {
+    data: [[L; 5]; 5],
+}
+
+// SAFETY: State is a transparent wrapper around an array of Pod lanes
+unsafe impl<L: Lane> Zeroable for State<L> {}
+unsafe impl<L: Lane> Pod for State<L> {}
+
+impl<L: Lane> From<[[L; 5]; 5]> for State<L> {
+    fn from(value: [[L; 5]; 5]) -> Self {
         State { data: value }
     }
 }
@@ -51,8 +128,25 @@ impl From<State> for [u8; 200] {
     }
 }
 
-pub fn theta(state: &mut State) -> &mut State {
-    let mut c = [0; 5];
+impl<L: Lane> State<L> {
+    /// Parses a state of `b / 8` bytes, with little-endian lanes.
+    ///
+    /// # Panics
+    /// If `bytes` does not have the size of the state.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut state = Self::default();
+        bytemuck::bytes_of_mut(&mut state).copy_from_slice(bytes);
+        state
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+#[allow(clippy::needless_range_loop)]
+pub fn theta<L: Lane>(state: &mut State<L>) -> &mut State<L> {
+    let mut c = [L::default(); 5];
     for x in 0..5 {
         for y in 0..5 {
             c[x] ^= state.data[y][x];
@@ -91,16 +185,18 @@ const fn compute_rho_offsets() -> [[u32; 5]; 5] {
 // (measured using cargo-flamegraph and hashing a 2GB file)
 const RHO_OFFSETS: [[u32; 5]; 5] = compute_rho_offsets();
 
-pub fn rho(state: &mut State) -> &mut State {
+// offsets are taken modulo the lane width
+#[allow(clippy::needless_range_loop)]
+pub fn rho<L: Lane>(state: &mut State<L>) -> &mut State<L> {
     for y in 0..5 {
         for x in 0..5 {
-            state.data[y][x] = state.data[y][x].rotate_left(RHO_OFFSETS[y][x]);
+            state.data[y][x] = state.data[y][x].rotate_left(RHO_OFFSETS[y][x] % L::BITS);
         }
     }
     state
 }
 
-pub fn pi(state: &mut State) -> &mut State {
+pub fn pi<L: Lane>(state: &mut State<L>) -> &mut State<L> {
     let mut new = State::default();
     for x in 0..5 {
         for y in 0..5 {
@@ -111,9 +207,9 @@ pub fn pi(state: &mut State) -> &mut State {
     state
 }
 
-pub fn chi(state: &mut State) -> &mut State {
+pub fn chi<L: Lane>(state: &mut State<L>) -> &mut State<L> {
     for row in &mut state.data {
-        let mut new = [0; 5];
+        let mut new = [L::default(); 5];
         for x in 0..5 {
             new[x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5])
         }
@@ -155,47 +251,98 @@ pub const fn make_round_constants<const N: usize>() -> [u64; N] {
 
 const RC: [u64; 24] = make_round_constants();
 
-pub fn iota(state: &mut State, ir: usize) -> &mut State {
+// the bits of RC at positions 2^j - 1 with j > ℓ do not fit in the lane,
+// so truncating the constant gives the round constant of Keccak-f[b]
+pub fn iota<L: Lane>(state: &mut State<L>, ir: usize) -> &mut State<L> {
     assert!(ir < 24, "Invalid round index");
 
-    state.data[0][0] ^= RC[ir];
+    state.data[0][0] ^= L::truncate(RC[ir]);
 
     state
 }
 
-pub fn round(state: &mut State, index: usize) {
+pub fn round<L: Lane>(state: &mut State<L>, index: usize) {
     iota(chi(pi(rho(theta(state)))), index);
 }
 
+/// Keccak-f\[b\], made of [`Lane::ROUNDS`] rounds of the step functions.
+pub fn keccak_f<L: Lane>(state: &mut State<L>) {
+    for index in 0..L::ROUNDS {
+        round(state, index);
+    }
+}
+
 // will panic if data is not aligned to 8
 // (required to cast &mut [u8; 200] to &mut State)
 pub fn keccak_p(data: &mut AlignedData) {
-    let state = bytemuck::must_cast_mut(data);
+    keccak_p_rounds(data, 24);
+}
+
+// Keccak-p[1600, rounds], i.e. only the last `rounds` rounds of keccak_p
+pub fn keccak_p_rounds(data: &mut AlignedData, rounds: usize) {
+    optimized::keccak_p1600(bytemuck::must_cast_mut(data), rounds);
+}
+
+// round-reduced permutation used by TurboSHAKE and KangarooTwelve
+pub fn keccak_p12(data: &mut AlignedData) {
+    keccak_p_rounds(data, 12);
+}
+
+// step-by-step version of keccak_p, kept as a reference for the optimized permutation
+pub fn keccak_p_reference(data: &mut AlignedData) {
+    let state: &mut State = bytemuck::must_cast_mut(data);
 
     for index in 0..24 {
         round(state, index);
     }
 }
 
-#[repr(u8)]
+/// Domain separation bits appended to the message before the pad10*1 padding.
+///
+/// The bits are stored least significant bit first: the suffix "01" of SHA-3
+/// is `Suffix::new(0b10, 2)`. At most 7 bits are allowed, so that the suffix
+/// and the first padding bit fit in a single byte.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum Suffix {
-    S01 = 0b10,
-    S1111 = 0b1111,
+pub struct Suffix {
+    bits: u8,
+    len: u8,
 }
 
 impl Suffix {
+    /// No suffix, as in the original Keccak submission
+    pub const NONE: Self = Self::new(0, 0);
+    /// cSHAKE
+    pub const S00: Self = Self::new(0b00, 2);
+    /// SHA-3
+    pub const S01: Self = Self::new(0b10, 2);
+    /// SHAKE
+    pub const S1111: Self = Self::new(0b1111, 4);
+
+    pub const fn new(bits: u8, len: u8) -> Self {
+        assert!(len <= 7, "Suffix must be at most 7 bits long");
+        assert!(bits >> len == 0, "Suffix bits do not fit in its length");
+        Self { bits, len }
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.bits
+    }
+
+    #[allow(clippy::len_without_is_empty)]
     pub const fn len(self) -> u8 {
-        match self {
-            Self::S01 => 2,
-            Self::S1111 => 4,
-        }
+        self.len
+    }
+
+    /// The suffix followed by pad10*1's first "1"
+    pub const fn domain_byte(self) -> u8 {
+        self.bits | (1 << self.len)
     }
 }
 
 pub trait KeccakFlavour {
     const CAPACITY: usize;
     const SUFFIX: Suffix;
+    const ROUNDS: usize = 24;
 }
 
 // bypass Rust's const generics limitations
@@ -211,87 +358,84 @@ impl<T: KeccakFlavour> Rate for T {}
 
 // using readers/iterators allows us to fetch bytes as-needed,
 // and not load the entire data range in memory
-pub fn keccak<F: KeccakFlavour>(mut msg: impl Read) -> io::Result<impl Iterator<Item = u8>> {
-    let mut data = AlignedData([0; 200]);
-
-    // absorption
-
-    loop {
-        let mut buf = [0; 200];
-        let n = msg.read_all(&mut buf[..F::R])?;
-        data.iter_mut().zip(&buf[..F::R]).for_each(|(s, p)| *s ^= p);
-
-        assert!(n <= F::R);
-
-        if n == F::R {
-            keccak_p(&mut data);
-        } else {
-            data[n] ^= F::SUFFIX as u8 | (1 << F::SUFFIX.len()); // suffix and pad10*1's first "1"
-            data[F::R - 1] ^= 0b1000_0000; // pad10*1's final "1"
-            keccak_p(&mut data);
-
-            break;
-        }
-    }
-
-    // squeezing
-
-    let mut current = 0;
-
-    Ok(std::iter::repeat_with(move || {
-        if current < F::R {
-            let byte = data[current];
-            current += 1;
-            byte
-        } else {
-            keccak_p(&mut data);
-            let byte = data[0];
-            current = 1;
-            byte
-        }
-    }))
+pub fn keccak<F: KeccakFlavour>(mut msg: impl Read) -> io::Result<SpongeReader<F>> {
+    let mut sponge = Sponge::new();
+    io::copy(&mut msg, &mut sponge)?;
+    Ok(sponge.finalize_xof())
 }
 
 // Keccak Flavour for extendable output functions
-struct Shake<const SIZE: usize>;
+#[derive(Debug, Copy, Clone)]
+pub struct Shake<const SIZE: usize>;
 
 impl<const SIZE: usize> KeccakFlavour for Shake<SIZE> {
     const CAPACITY: usize = 2 * SIZE;
     const SUFFIX: Suffix = Suffix::S1111;
 }
 
-pub fn shake128(msg: impl Read) -> io::Result<impl Iterator<Item = u8>> {
+pub fn shake128(msg: impl Read) -> io::Result<Shake128Reader> {
     keccak::<Shake<128>>(msg)
 }
 
-pub fn shake256(msg: impl Read) -> io::Result<impl Iterator<Item = u8>> {
+pub fn shake256(msg: impl Read) -> io::Result<Shake256Reader> {
     keccak::<Shake<256>>(msg)
 }
 
 // Keccak Flavour for fixed size hash functions
-struct Sha3<const SIZE: usize>;
+#[derive(Debug, Copy, Clone)]
+pub struct Sha3<const SIZE: usize>;
 
 impl<const SIZE: usize> KeccakFlavour for Sha3<SIZE> {
     const CAPACITY: usize = 2 * SIZE;
     const SUFFIX: Suffix = Suffix::S01;
 }
 
-fn sha3<const SIZE: usize>(msg: impl Read) -> io::Result<Vec<u8>> {
-    keccak::<Sha3<SIZE>>(msg).map(|hash| hash.take(SIZE / 8).collect())
+// Keccak Flavour for the original Keccak submission, without domain separation suffix
+#[derive(Debug, Copy, Clone)]
+pub struct Keccak<const SIZE: usize>;
+
+impl<const SIZE: usize> KeccakFlavour for Keccak<SIZE> {
+    const CAPACITY: usize = 2 * SIZE;
+    const SUFFIX: Suffix = Suffix::NONE;
+}
+
+// N must be SIZE / 8 bytes, which const generics cannot compute for us
+fn fixed_output<F: KeccakFlavour, const N: usize>(msg: impl Read) -> io::Result<[u8; N]> {
+    let mut digest = [0; N];
+    keccak::<F>(msg)?.squeeze(&mut digest);
+    Ok(digest)
+}
+
+pub fn sha3_224(msg: impl Read) -> io::Result<[u8; 28]> {
+    fixed_output::<Sha3<224>, 28>(msg)
 }
 
-pub fn sha3_224(msg: impl Read) -> io::Result<Vec<u8>> {
-    sha3::<224>(msg)
+pub fn sha3_256(msg: impl Read) -> io::Result<[u8; 32]> {
+    fixed_output::<Sha3<256>, 32>(msg)
+}
+
+pub fn sha3_384(msg: impl Read) -> io::Result<[u8; 48]> {
+    fixed_output::<Sha3<384>, 48>(msg)
+}
+
+pub fn sha3_512(msg: impl Read) -> io::Result<[u8; 64]> {
+    fixed_output::<Sha3<512>, 64>(msg)
+}
+
+// the original Keccak padding, used by Ethereum's keccak256 among others
+
+pub fn keccak224(msg: impl Read) -> io::Result<[u8; 28]> {
+    fixed_output::<Keccak<224>, 28>(msg)
 }
 
-pub fn sha3_256(msg: impl Read) -> io::Result<Vec<u8>> {
-    sha3::<256>(msg)
+pub fn keccak256(msg: impl Read) -> io::Result<[u8; 32]> {
+    fixed_output::<Keccak<256>, 32>(msg)
 }
 
-pub fn sha3_384(msg: impl Read) -> io::Result<Vec<u8>> {
-    sha3::<384>(msg)
+pub fn keccak384(msg: impl Read) -> io::Result<[u8; 48]> {
+    fixed_output::<Keccak<384>, 48>(msg)
 }
 
-pub fn sha3_512(msg: impl Read) -> io::Result<Vec<u8>> {
-    sha3::<512>(msg)
+pub fn keccak512(msg: impl Read) -> io::Result<[u8; 64]> {
+    fixed_output::<Keccak<512>, 64>(msg)
 }
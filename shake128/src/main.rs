@@ -1,20 +1,283 @@
-use std::io::stdin;
+use std::fs::File;
+use std::io::{self, stdin, stdout, BufRead, BufReader, Read, Write};
+use std::process::ExitCode;
+use std::str::FromStr;
 
-use shake128::shake128;
+use argh::FromArgs;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use io_utils::hexfmt;
+use shake128::{sha3_224, sha3_256, sha3_384, sha3_512, shake128, shake256};
 
-fn main() {
-    let Some(num_bytes) = std::env::args()
-        .nth(1)
-        .and_then(|x| x.parse::<usize>().ok())
-    else {
-        eprintln!("Usage: shake128 <n>");
-        std::process::exit(1)
-    };
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Shake128,
+    Shake256,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sha3-224" => Ok(Self::Sha3_224),
+            "sha3-256" => Ok(Self::Sha3_256),
+            "sha3-384" => Ok(Self::Sha3_384),
+            "sha3-512" => Ok(Self::Sha3_512),
+            "shake128" => Ok(Self::Shake128),
+            "shake256" => Ok(Self::Shake256),
+            _ => Err(format!(
+                "unknown algorithm {s:?}, expected one of \
+                 sha3-224, sha3-256, sha3-384, sha3-512, shake128, shake256"
+            )),
+        }
+    }
+}
+
+impl Algorithm {
+    fn is_xof(self) -> bool {
+        matches!(self, Self::Shake128 | Self::Shake256)
+    }
+
+    // output length when none is given
+    fn default_len(self) -> usize {
+        match self {
+            Self::Sha3_224 => 28,
+            Self::Sha3_256 | Self::Shake128 => 32,
+            Self::Sha3_384 => 48,
+            Self::Sha3_512 | Self::Shake256 => 64,
+        }
+    }
+
+    // `len` is only used by the SHAKE functions
+    fn hash(self, msg: impl Read, len: usize) -> io::Result<Vec<u8>> {
+        Ok(match self {
+            Self::Sha3_224 => sha3_224(msg)?.to_vec(),
+            Self::Sha3_256 => sha3_256(msg)?.to_vec(),
+            Self::Sha3_384 => sha3_384(msg)?.to_vec(),
+            Self::Sha3_512 => sha3_512(msg)?.to_vec(),
+            Self::Shake128 => shake128(msg)?.take(len).collect(),
+            Self::Shake256 => shake256(msg)?.take(len).collect(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Hex,
+    Base64,
+    Raw,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "hex" => Ok(Self::Hex),
+            "base64" => Ok(Self::Base64),
+            "raw" => Ok(Self::Raw),
+            _ => Err(format!(
+                "unknown format {s:?}, expected one of hex, base64, raw"
+            )),
+        }
+    }
+}
+
+impl Format {
+    fn encode(self, digest: &[u8]) -> String {
+        match self {
+            Self::Hex => hexfmt(&digest).to_string(),
+            Self::Base64 => BASE64.encode(digest),
+            Self::Raw => unreachable!("raw digests are not printed as text"),
+        }
+    }
+
+    fn decode(self, digest: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Hex => decode_hex(digest),
+            Self::Base64 => BASE64.decode(digest).ok(),
+            Self::Raw => None,
+        }
+    }
+}
+
+// digests of SHAKE functions have any length, unlike what `io_utils::parse_hex` expects
+fn decode_hex(digest: &str) -> Option<Vec<u8>> {
+    // `from_str_radix` alone would also accept a sign
+    if !digest.len().is_multiple_of(2) || !digest.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..digest.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digest[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Print or check SHA-3 and SHAKE checksums
+///
+/// With no file, or when file is "_", read standard input.
+/// Each checksum is printed as "<digest>  <file>", which is also the
+/// format of the lines read by --check.
+#[derive(FromArgs)]
+struct Opts {
+    /// hash function: sha3-224, sha3-256, sha3-384, sha3-512,
+    /// shake128 (default) or shake256
+    #[argh(option, short = 'a', default = "Algorithm::Shake128")]
+    algorithm: Algorithm,
+    /// output length in bytes, for shake128 (default 32) and shake256 (default 64)
+    #[argh(option, short = 'l')]
+    length: Option<usize>,
+    /// output format: hex (default), base64 or raw (digests only, without file names)
+    #[argh(option, short = 'f', default = "Format::Hex")]
+    format: Format,
+    /// read checksums from the files and check them
+    #[argh(switch, short = 'c')]
+    check: bool,
+    /// files to hash, or checksum files with --check
+    #[argh(positional)]
+    files: Vec<String>,
+}
 
-    let hash = shake128(stdin().lock()).expect("Could not read data from stdin");
+fn open(path: &str) -> io::Result<Box<dyn Read>> {
+    Ok(match path {
+        "_" | "-" => Box::new(stdin().lock()),
+        path => Box::new(File::open(path)?),
+    })
+}
+
+fn hash_file(path: &str, algorithm: Algorithm, len: usize) -> io::Result<Vec<u8>> {
+    algorithm.hash(BufReader::new(open(path)?), len)
+}
+
+// prints the digest of each file, returns whether all of them could be read
+fn print_checksums(opts: &Opts, len: usize) -> bool {
+    let mut ok = true;
+    let mut out = stdout().lock();
+
+    for path in &opts.files {
+        let digest = match hash_file(path, opts.algorithm, len) {
+            Ok(digest) => digest,
+            Err(e) => {
+                eprintln!("shake128: {path}: {e}");
+                ok = false;
+                continue;
+            }
+        };
+
+        let written = match opts.format {
+            Format::Raw => out.write_all(&digest),
+            format => writeln!(out, "{}  {path}", format.encode(&digest)),
+        };
+        written.expect("Error while writing to stdout");
+    }
+
+    ok
+}
+
+// checks each line of each checksum file, returns whether all of them matched.
+// Digests must have the length of the algorithm, or the one given for SHAKE
+fn check_checksums(opts: &Opts, len: usize) -> bool {
+    let mut failed = 0;
+    let mut unreadable = 0;
+    let mut malformed = 0;
+
+    for path in &opts.files {
+        let lines = match open(path) {
+            Ok(file) => BufReader::new(file).lines(),
+            Err(e) => {
+                eprintln!("shake128: {path}: {e}");
+                unreadable += 1;
+                continue;
+            }
+        };
+
+        for line in lines {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    eprintln!("shake128: {path}: {e}");
+                    unreadable += 1;
+                    break;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // "<digest>  <file>", or "<digest> *<file>" for files hashed in binary mode
+            let parsed = line
+                .split_once(' ')
+                .map(|(digest, file)| (digest, file.strip_prefix([' ', '*']).unwrap_or(file)))
+                .and_then(|(digest, file)| Some((opts.format.decode(digest)?, file)))
+                .filter(|(digest, _)| digest.len() == len);
+            let Some((expected, file)) = parsed else {
+                malformed += 1;
+                continue;
+            };
+
+            match hash_file(file, opts.algorithm, len) {
+                Ok(digest) if digest == expected => println!("{file}: OK"),
+                Ok(_) => {
+                    println!("{file}: FAILED");
+                    failed += 1;
+                }
+                Err(e) => {
+                    eprintln!("shake128: {file}: {e}");
+                    println!("{file}: FAILED open or read");
+                    unreadable += 1;
+                }
+            }
+        }
+    }
+
+    if malformed > 0 {
+        eprintln!("shake128: WARNING: {malformed} line(s) are improperly formatted");
+    }
+    if unreadable > 0 {
+        eprintln!("shake128: WARNING: {unreadable} listed file(s) could not be read");
+    }
+    if failed > 0 {
+        eprintln!("shake128: WARNING: {failed} computed checksum(s) did NOT match");
+    }
+
+    failed == 0 && unreadable == 0 && malformed == 0
+}
+
+fn main() -> ExitCode {
+    let mut opts: Opts = argh::from_env();
+
+    if opts.files.is_empty() {
+        opts.files.push("_".into());
+    }
+
+    if opts.length.is_some() && !opts.algorithm.is_xof() {
+        eprintln!("shake128: --length only applies to shake128 and shake256");
+        return ExitCode::FAILURE;
+    }
+    if opts.length == Some(0) {
+        eprintln!("shake128: --length must be at least 1");
+        return ExitCode::FAILURE;
+    }
+    if opts.check && opts.format == Format::Raw {
+        eprintln!("shake128: --check does not support the raw format");
+        return ExitCode::FAILURE;
+    }
+
+    let len = opts.length.unwrap_or(opts.algorithm.default_len());
+    let ok = if opts.check {
+        check_checksums(&opts, len)
+    } else {
+        print_checksums(&opts, len)
+    };
 
-    for output in hash.take(num_bytes) {
-        print!("{output:02x}");
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
     }
-    println!()
 }
@@ -0,0 +1,133 @@
+//! Optimized Keccak-f\[1600\] permutation
+//!
+//! This computes the same function as chaining [`theta`](crate::theta), [`rho`](crate::rho),
+//! [`pi`](crate::pi), [`chi`](crate::chi) and [`iota`](crate::iota), but:
+//! - all five steps are merged in a single pass over the lanes, fully unrolled,
+//!   so that lanes can live in registers;
+//! - pi is not a separate step: each lane is read from its pre-pi position,
+//!   and written straight to its final position in the other half of a double buffer;
+//! - six lanes are kept complemented between rounds ("lane complementing"),
+//!   which removes most of the NOT operations of chi.
+
+use crate::RC;
+
+// lanes that are stored complemented during the permutation
+// (indices 1, 2, 8, 12, 17 and 20, i.e. the "bebigokimisa" pattern)
+const COMPLEMENTED: [usize; 6] = [1, 2, 8, 12, 17, 20];
+
+fn complement(lanes: &mut [u64; 25]) {
+    for i in COMPLEMENTED {
+        lanes[i] = !lanes[i];
+    }
+}
+
+// one round from `a` to `e`.
+// lanes are indexed by 5 * y + x; B[y][x] is the lane at (x, y) after rho and pi.
+macro_rules! round {
+    ($a:ident, $e:ident, $rc:expr) => {
+        let c0 = $a[0] ^ $a[5] ^ $a[10] ^ $a[15] ^ $a[20];
+        let c1 = $a[1] ^ $a[6] ^ $a[11] ^ $a[16] ^ $a[21];
+        let c2 = $a[2] ^ $a[7] ^ $a[12] ^ $a[17] ^ $a[22];
+        let c3 = $a[3] ^ $a[8] ^ $a[13] ^ $a[18] ^ $a[23];
+        let c4 = $a[4] ^ $a[9] ^ $a[14] ^ $a[19] ^ $a[24];
+
+        let d0 = c4 ^ c1.rotate_left(1);
+        let d1 = c0 ^ c2.rotate_left(1);
+        let d2 = c1 ^ c3.rotate_left(1);
+        let d3 = c2 ^ c4.rotate_left(1);
+        let d4 = c3 ^ c0.rotate_left(1);
+
+        // plane y = 0
+        let b0 = $a[0] ^ d0;
+        let b1 = ($a[6] ^ d1).rotate_left(44);
+        let b2 = ($a[12] ^ d2).rotate_left(43);
+        let b3 = ($a[18] ^ d3).rotate_left(21);
+        let b4 = ($a[24] ^ d4).rotate_left(14);
+        $e[0] = b0 ^ (b1 | b2) ^ $rc;
+        $e[1] = b1 ^ (!b2 | b3);
+        $e[2] = b2 ^ (b3 & b4);
+        $e[3] = b3 ^ (b4 | b0);
+        $e[4] = b4 ^ (b0 & b1);
+
+        // plane y = 1
+        let b0 = ($a[3] ^ d3).rotate_left(28);
+        let b1 = ($a[9] ^ d4).rotate_left(20);
+        let b2 = ($a[10] ^ d0).rotate_left(3);
+        let b3 = ($a[16] ^ d1).rotate_left(45);
+        let b4 = ($a[22] ^ d2).rotate_left(61);
+        $e[5] = b0 ^ (b1 | b2);
+        $e[6] = b1 ^ (b2 & b3);
+        $e[7] = b2 ^ (b3 | !b4);
+        $e[8] = b3 ^ (b4 | b0);
+        $e[9] = b4 ^ (b0 & b1);
+
+        // plane y = 2
+        let b0 = ($a[1] ^ d1).rotate_left(1);
+        let b1 = ($a[7] ^ d2).rotate_left(6);
+        let b2 = ($a[13] ^ d3).rotate_left(25);
+        let b3 = ($a[19] ^ d4).rotate_left(8);
+        let b4 = ($a[20] ^ d0).rotate_left(18);
+        $e[10] = b0 ^ (b1 | b2);
+        $e[11] = b1 ^ (b2 & b3);
+        $e[12] = b2 ^ (!b3 & b4);
+        $e[13] = !b3 ^ (b4 | b0);
+        $e[14] = b4 ^ (b0 & b1);
+
+        // plane y = 3
+        let b0 = ($a[4] ^ d4).rotate_left(27);
+        let b1 = ($a[5] ^ d0).rotate_left(36);
+        let b2 = ($a[11] ^ d1).rotate_left(10);
+        let b3 = ($a[17] ^ d2).rotate_left(15);
+        let b4 = ($a[23] ^ d3).rotate_left(56);
+        $e[15] = b0 ^ (b1 & b2);
+        $e[16] = b1 ^ (b2 | b3);
+        $e[17] = b2 ^ (!b3 | b4);
+        $e[18] = !b3 ^ (b4 & b0);
+        $e[19] = b4 ^ (b0 | b1);
+
+        // plane y = 4
+        let b0 = ($a[2] ^ d2).rotate_left(62);
+        let b1 = ($a[8] ^ d3).rotate_left(55);
+        let b2 = ($a[14] ^ d4).rotate_left(39);
+        let b3 = ($a[15] ^ d0).rotate_left(41);
+        let b4 = ($a[21] ^ d1).rotate_left(2);
+        $e[20] = b0 ^ (!b1 & b2);
+        $e[21] = !b1 ^ (b2 | b3);
+        $e[22] = b2 ^ (b3 & b4);
+        $e[23] = b3 ^ (b4 | b0);
+        $e[24] = b4 ^ (b0 & b1);
+    };
+}
+
+/// Keccak-f\[1600\] over 25 lanes, indexed by `5 * y + x`.
+pub fn keccak_f1600(lanes: &mut [u64; 25]) {
+    keccak_p1600(lanes, 24);
+}
+
+/// Keccak-p\[1600, n\]: the last `rounds` rounds of Keccak-f\[1600\].
+pub fn keccak_p1600(lanes: &mut [u64; 25], rounds: usize) {
+    assert!(rounds <= 24, "Keccak-p[1600] has at most 24 rounds");
+
+    complement(lanes);
+
+    let mut a = *lanes;
+    let mut e = [0; 25];
+
+    let mut rc = &RC[24 - rounds..];
+    // an odd number of rounds starts with a single one,
+    // so that the rest can be processed in pairs
+    if rc.len() % 2 == 1 {
+        round!(a, e, rc[0]);
+        a = e;
+        rc = &rc[1..];
+    }
+
+    // two rounds per iteration, swapping the roles of the two buffers
+    for rc in rc.chunks_exact(2) {
+        round!(a, e, rc[0]);
+        round!(e, a, rc[1]);
+    }
+
+    *lanes = a;
+    complement(lanes);
+}
@@ -0,0 +1,133 @@
+//! ParallelHash, as defined in NIST SP 800-185
+//!
+//! The message is split into blocks of a fixed size, which are hashed
+//! independently on worker threads. The block digests are then hashed together.
+
+use std::{
+    io::{self, Cursor, Read},
+    thread,
+};
+
+use io_utils::ReadExt;
+
+use crate::cshake::{cshake, left_encode, right_encode};
+use crate::{Shake, Shake128Reader, Shake256Reader, SpongeReader};
+
+// amount of data read from the input before dispatching it to the workers,
+// unless a single block is larger
+const BATCH_SIZE: usize = 1 << 22;
+
+// each block is hashed with cSHAKE (without name nor customization),
+// with an output twice the security level
+fn hash_block<const SIZE: usize>(block: &[u8]) -> Vec<u8> {
+    // reading from memory cannot fail
+    cshake::<SIZE>(b"", b"", block)
+        .unwrap()
+        .take(SIZE / 4)
+        .collect()
+}
+
+fn parallel_hash_xof<const SIZE: usize>(
+    mut msg: impl Read,
+    block_size: usize,
+    output_bits: u64,
+    customization: &[u8],
+) -> io::Result<SpongeReader<Shake<SIZE>>> {
+    assert!(block_size > 0, "ParallelHash block size must be positive");
+
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    // the buffer holds at most BATCH_SIZE bytes, or one block if it is larger:
+    // large blocks are then hashed one at a time instead of one per worker
+    let blocks_per_batch = (BATCH_SIZE / block_size).max(1);
+
+    let mut data = left_encode(block_size as u64);
+    let mut blocks = 0;
+    let mut batch = vec![0; blocks_per_batch * block_size];
+
+    loop {
+        let n = msg.read_all(&mut batch)?;
+        let chunks: Vec<&[u8]> = batch[..n].chunks(block_size).collect();
+
+        // split the blocks evenly between the workers, and collect
+        // the digests in the original block order
+        let per_worker = chunks.len().div_ceil(workers).max(1);
+        let digests = thread::scope(|s| {
+            let handles: Vec<_> = chunks
+                .chunks(per_worker)
+                .map(|group| {
+                    s.spawn(move || {
+                        group
+                            .iter()
+                            .flat_map(|block| hash_block::<SIZE>(block))
+                            .collect::<Vec<u8>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("ParallelHash worker panicked"))
+                .collect::<Vec<u8>>()
+        });
+
+        data.extend_from_slice(&digests);
+        blocks += chunks.len() as u64;
+
+        if n < batch.len() {
+            break;
+        }
+    }
+
+    data.extend_from_slice(&right_encode(blocks));
+    data.extend_from_slice(&right_encode(output_bits));
+
+    cshake::<SIZE>(b"ParallelHash", customization, Cursor::new(data))
+}
+
+fn parallel_hash<const SIZE: usize>(
+    msg: impl Read,
+    block_size: usize,
+    len: usize,
+    customization: &[u8],
+) -> io::Result<Vec<u8>> {
+    parallel_hash_xof::<SIZE>(msg, block_size, 8 * len as u64, customization)
+        .map(|hash| hash.take(len).collect())
+}
+
+/// ParallelHash128 of `msg` split in blocks of `block_size` bytes, with an output of `len` bytes.
+pub fn parallel_hash128(
+    msg: impl Read,
+    block_size: usize,
+    len: usize,
+    customization: &[u8],
+) -> io::Result<Vec<u8>> {
+    parallel_hash::<128>(msg, block_size, len, customization)
+}
+
+/// ParallelHash256 of `msg` split in blocks of `block_size` bytes, with an output of `len` bytes.
+pub fn parallel_hash256(
+    msg: impl Read,
+    block_size: usize,
+    len: usize,
+    customization: &[u8],
+) -> io::Result<Vec<u8>> {
+    parallel_hash::<256>(msg, block_size, len, customization)
+}
+
+/// ParallelHashXOF128 of `msg` split in blocks of `block_size` bytes.
+pub fn parallel_hash_xof128(
+    msg: impl Read,
+    block_size: usize,
+    customization: &[u8],
+) -> io::Result<Shake128Reader> {
+    parallel_hash_xof::<128>(msg, block_size, 0, customization)
+}
+
+/// ParallelHashXOF256 of `msg` split in blocks of `block_size` bytes.
+pub fn parallel_hash_xof256(
+    msg: impl Read,
+    block_size: usize,
+    customization: &[u8],
+) -> io::Result<Shake256Reader> {
+    parallel_hash_xof::<256>(msg, block_size, 0, customization)
+}
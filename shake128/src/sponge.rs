@@ -0,0 +1,282 @@
+//! Incremental sponge construction over Keccak-f\[1600\]
+//!
+//! Unlike [`keccak`](crate::keccak), which consumes a single reader, a [`Sponge`]
+//! absorbs data piecemeal and can be cloned to fork its midstate.
+
+use std::{
+    io::{self, Read, Seek, SeekFrom, Write},
+    marker::PhantomData,
+};
+
+use crate::{keccak_p_rounds, AlignedData, KeccakFlavour, Rate, Sha3, Shake, Suffix};
+
+/// Absorbing half of a sponge function of flavour `F`.
+#[derive(Debug)]
+pub struct Sponge<F: KeccakFlavour> {
+    data: AlignedData,
+    pos: usize,
+    flavour: PhantomData<F>,
+}
+
+// not derived, to avoid requiring F: Clone
+impl<F: KeccakFlavour> Clone for Sponge<F> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            pos: self.pos,
+            flavour: PhantomData,
+        }
+    }
+}
+
+impl<F: KeccakFlavour> Default for Sponge<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: KeccakFlavour> Sponge<F> {
+    pub fn new() -> Self {
+        Self {
+            data: AlignedData([0; 200]),
+            pos: 0,
+            flavour: PhantomData,
+        }
+    }
+
+    /// Absorbs `data` into the sponge. Calling this repeatedly is the same as
+    /// calling it once on the concatenation of the inputs.
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let n = data.len().min(F::R - self.pos);
+            self.data[self.pos..self.pos + n]
+                .iter_mut()
+                .zip(&data[..n])
+                .for_each(|(s, p)| *s ^= p);
+
+            self.pos += n;
+            data = &data[n..];
+
+            if self.pos == F::R {
+                keccak_p_rounds(&mut self.data, F::ROUNDS);
+                self.pos = 0;
+            }
+        }
+    }
+
+    /// Pads the message with the flavour's suffix and switches to squeezing.
+    pub fn finalize_xof(self) -> SpongeReader<F> {
+        self.finalize_with(F::SUFFIX)
+    }
+
+    // padding with another suffix than the flavour's own,
+    // for constructions that share a rate but not a domain (such as cSHAKE)
+    pub(crate) fn finalize_with(self, suffix: Suffix) -> SpongeReader<F> {
+        self.finalize_with_domain(suffix.domain_byte())
+    }
+
+    // `domain` holds both the suffix bits and the first padding bit,
+    // as in the domain separation byte of TurboSHAKE
+    pub(crate) fn finalize_with_domain(mut self, domain: u8) -> SpongeReader<F> {
+        self.data[self.pos] ^= domain;
+        self.data[F::R - 1] ^= 0b1000_0000; // pad10*1's final "1"
+        keccak_p_rounds(&mut self.data, F::ROUNDS);
+
+        SpongeReader {
+            data: self.data,
+            pos: 0,
+            flavour: PhantomData,
+        }
+    }
+}
+
+impl<F: KeccakFlavour> Write for Sponge<F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Squeezing half of a sponge function of flavour `F`.
+///
+/// The output is infinite, and can be consumed in bulk with [`SpongeReader::squeeze`]
+/// or byte by byte as an [`Iterator`]. For random access, see [`SpongeReader::seekable`].
+#[derive(Debug)]
+pub struct SpongeReader<F: KeccakFlavour> {
+    data: AlignedData,
+    pos: usize,
+    flavour: PhantomData<F>,
+}
+
+impl<F: KeccakFlavour> Clone for SpongeReader<F> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            pos: self.pos,
+            flavour: PhantomData,
+        }
+    }
+}
+
+impl<F: KeccakFlavour> SpongeReader<F> {
+    /// Fills `out` with the next bytes of output, a rate-sized block at a time.
+    pub fn squeeze(&mut self, mut out: &mut [u8]) {
+        while !out.is_empty() {
+            if self.pos == F::R {
+                keccak_p_rounds(&mut self.data, F::ROUNDS);
+                self.pos = 0;
+            }
+
+            let n = out.len().min(F::R - self.pos);
+            out[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+
+            self.pos += n;
+            out = &mut out[n..];
+        }
+    }
+
+    /// Turns this reader into one that implements [`Read`] and [`Seek`].
+    /// Positions are counted from the current position of this reader.
+    pub fn seekable(self) -> SeekableReader<F> {
+        SeekableReader {
+            origin: self.data,
+            origin_pos: self.pos,
+            data: self.data,
+            block: 0,
+            pos: self.pos,
+            flavour: PhantomData,
+        }
+    }
+}
+
+impl<F: KeccakFlavour> Iterator for SpongeReader<F> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let mut byte = [0];
+        self.squeeze(&mut byte);
+        Some(byte[0])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Squeezing half of a sponge function, with random access to its output.
+///
+/// Seeking forward runs the permutation once per skipped block, without
+/// producing any output. Seeking backward starts over from the first block.
+/// The output has no end: seeking relative to [`SeekFrom::End`] fails.
+#[derive(Debug)]
+pub struct SeekableReader<F: KeccakFlavour> {
+    // the state in which position 0 lies, at offset `origin_pos`
+    origin: AlignedData,
+    origin_pos: usize,
+    // the state `block` permutations after `origin`
+    data: AlignedData,
+    block: u64,
+    pos: usize,
+    flavour: PhantomData<F>,
+}
+
+impl<F: KeccakFlavour> Clone for SeekableReader<F> {
+    fn clone(&self) -> Self {
+        Self {
+            origin: self.origin,
+            origin_pos: self.origin_pos,
+            data: self.data,
+            block: self.block,
+            pos: self.pos,
+            flavour: PhantomData,
+        }
+    }
+}
+
+impl<F: KeccakFlavour> SeekableReader<F> {
+    /// Current position in the output, in bytes.
+    pub fn position(&self) -> u64 {
+        self.block * F::R as u64 + self.pos as u64 - self.origin_pos as u64
+    }
+
+    fn seek_to(&mut self, position: u64) -> io::Result<u64> {
+        let offset = position
+            .checked_add(self.origin_pos as u64)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "seek position overflow"))?;
+        let (block, pos) = (offset / F::R as u64, (offset % F::R as u64) as usize);
+
+        if block < self.block {
+            self.data = self.origin;
+            self.block = 0;
+        }
+        for _ in self.block..block {
+            keccak_p_rounds(&mut self.data, F::ROUNDS);
+        }
+
+        self.block = block;
+        self.pos = pos;
+        Ok(position)
+    }
+}
+
+impl<F: KeccakFlavour> Read for SeekableReader<F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut out = &mut *buf;
+        while !out.is_empty() {
+            if self.pos == F::R {
+                keccak_p_rounds(&mut self.data, F::ROUNDS);
+                self.block += 1;
+                self.pos = 0;
+            }
+
+            let n = out.len().min(F::R - self.pos);
+            out[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+
+            self.pos += n;
+            out = &mut out[n..];
+        }
+
+        Ok(buf.len())
+    }
+}
+
+impl<F: KeccakFlavour> Seek for SeekableReader<F> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => self.position().checked_add_signed(n),
+            SeekFrom::End(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "extendable output has no end",
+                ))
+            }
+        };
+
+        let position = position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        self.seek_to(position)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.position())
+    }
+}
+
+pub type Shake128Hasher = Sponge<Shake<128>>;
+pub type Shake256Hasher = Sponge<Shake<256>>;
+pub type Shake128Reader = SpongeReader<Shake<128>>;
+pub type Shake256Reader = SpongeReader<Shake<256>>;
+
+pub type Sha3_224Hasher = Sponge<Sha3<224>>;
+pub type Sha3_256Hasher = Sponge<Sha3<256>>;
+pub type Sha3_384Hasher = Sponge<Sha3<384>>;
+pub type Sha3_512Hasher = Sponge<Sha3<512>>;
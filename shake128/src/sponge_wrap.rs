@@ -0,0 +1,120 @@
+//! SpongeWrap authenticated encryption, from "Duplexing the sponge" (Bertoni et al.)
+//!
+//! Key, associated data and message are cut in blocks of `RATE - 1` bytes and fed
+//! to a [`Duplex128`]. Each block carries a frame bit telling whether it is the last
+//! one of its kind. Each plaintext block is encrypted with the output of the
+//! previous duplex call, and the tag is squeezed after the last one.
+//!
+//! The nonce is absorbed together with the key: a (key, nonce) pair must never
+//! be used to seal two different messages.
+
+use std::io::{self, Read, Write};
+
+use io_utils::{ct_eq, ReadExt};
+
+use crate::duplex::Duplex128;
+use crate::Suffix;
+
+const BLOCK: usize = Duplex128::RATE - 1;
+
+// frame bits, appended to each block before padding
+const FRAME_0: Suffix = Suffix::new(0, 1);
+const FRAME_1: Suffix = Suffix::new(1, 1);
+
+// reads `data` in blocks, calling `f(block, is_last)` on each of them.
+// an empty input is a single empty block
+fn for_each_block(
+    mut data: impl Read,
+    mut f: impl FnMut(&[u8], bool) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut block = [0; BLOCK];
+    let mut next = [0; BLOCK];
+
+    let mut n = data.read_all(&mut block)?;
+    loop {
+        // only a full block may be followed by more data
+        let m = if n == BLOCK {
+            data.read_all(&mut next)?
+        } else {
+            0
+        };
+        let last = m == 0;
+
+        f(&block[..n], last)?;
+        if last {
+            return Ok(());
+        }
+
+        std::mem::swap(&mut block, &mut next);
+        n = m;
+    }
+}
+
+// runs SpongeWrap over `input`, either a plaintext (encrypting) or a ciphertext
+// (decrypting), and returns the tag. Plaintext blocks are the ones that get absorbed.
+fn wrap(
+    key: &[u8; 32],
+    nonce: &[u8; 16],
+    ad: impl Read,
+    input: impl Read,
+    mut output: impl Write,
+    decrypt: bool,
+) -> io::Result<[u8; 16]> {
+    let mut duplex = Duplex128::new();
+    let mut keystream = [0; Duplex128::RATE];
+
+    // key and nonce fit in a single (last) key block
+    let mut secret = [0; 48];
+    secret[..32].copy_from_slice(key);
+    secret[32..].copy_from_slice(nonce);
+    duplex.duplex_with(&secret, FRAME_0);
+
+    for_each_block(ad, |block, last| {
+        let frame = if last { FRAME_1 } else { FRAME_0 };
+        keystream.copy_from_slice(duplex.duplex_with(block, frame));
+        Ok(())
+    })?;
+
+    let mut out = [0; BLOCK];
+    for_each_block(input, |block, last| {
+        let out = &mut out[..block.len()];
+        out.iter_mut()
+            .zip(block.iter().zip(&keystream))
+            .for_each(|(o, (b, k))| *o = b ^ k);
+        output.write_all(out)?;
+
+        let plaintext = if decrypt { &*out } else { block };
+        let frame = if last { FRAME_0 } else { FRAME_1 };
+        keystream.copy_from_slice(duplex.duplex_with(plaintext, frame));
+        Ok(())
+    })?;
+
+    Ok(keystream[..16].try_into().unwrap())
+}
+
+/// Encrypts `plaintext` into `ciphertext`, and returns the tag
+/// authenticating both the ciphertext and `ad`.
+pub fn seal(
+    key: &[u8; 32],
+    nonce: &[u8; 16],
+    ad: impl Read,
+    plaintext: impl Read,
+    ciphertext: impl Write,
+) -> io::Result<[u8; 16]> {
+    wrap(key, nonce, ad, plaintext, ciphertext, false)
+}
+
+/// Decrypts `ciphertext` into `plaintext`, and checks `tag` in constant time.
+///
+/// The plaintext is written as it is decrypted, before the tag can be checked:
+/// if this returns `false`, everything written to `plaintext` must be discarded.
+pub fn open(
+    key: &[u8; 32],
+    nonce: &[u8; 16],
+    ad: impl Read,
+    ciphertext: impl Read,
+    tag: &[u8; 16],
+    plaintext: impl Write,
+) -> io::Result<bool> {
+    wrap(key, nonce, ad, ciphertext, plaintext, true).map(|computed| ct_eq(&computed, tag))
+}
@@ -0,0 +1,54 @@
+//! TupleHash, as defined in NIST SP 800-185
+//!
+//! Each element of the tuple is length-prefixed before being hashed,
+//! so `("ab", "c")` and `("a", "bc")` give unrelated digests.
+
+use std::io::Cursor;
+
+use crate::cshake::{cshake, encode_string, right_encode};
+use crate::{Shake, Shake128Reader, Shake256Reader, SpongeReader};
+
+fn tuple_hash_xof<const SIZE: usize>(
+    inputs: &[impl AsRef<[u8]>],
+    output_bits: u64,
+    customization: &[u8],
+) -> SpongeReader<Shake<SIZE>> {
+    let mut data = Vec::new();
+    for input in inputs {
+        data.extend_from_slice(&encode_string(input.as_ref()));
+    }
+    data.extend_from_slice(&right_encode(output_bits));
+
+    // reading from memory cannot fail
+    cshake::<SIZE>(b"TupleHash", customization, Cursor::new(data)).unwrap()
+}
+
+fn tuple_hash<const SIZE: usize>(
+    inputs: &[impl AsRef<[u8]>],
+    len: usize,
+    customization: &[u8],
+) -> Vec<u8> {
+    tuple_hash_xof::<SIZE>(inputs, 8 * len as u64, customization)
+        .take(len)
+        .collect()
+}
+
+/// TupleHash128 of `inputs`, with an output of `len` bytes.
+pub fn tuple_hash128(inputs: &[impl AsRef<[u8]>], len: usize, customization: &[u8]) -> Vec<u8> {
+    tuple_hash::<128>(inputs, len, customization)
+}
+
+/// TupleHash256 of `inputs`, with an output of `len` bytes.
+pub fn tuple_hash256(inputs: &[impl AsRef<[u8]>], len: usize, customization: &[u8]) -> Vec<u8> {
+    tuple_hash::<256>(inputs, len, customization)
+}
+
+/// TupleHashXOF128 of `inputs`, with an arbitrary output length.
+pub fn tuple_hash_xof128(inputs: &[impl AsRef<[u8]>], customization: &[u8]) -> Shake128Reader {
+    tuple_hash_xof::<128>(inputs, 0, customization)
+}
+
+/// TupleHashXOF256 of `inputs`, with an arbitrary output length.
+pub fn tuple_hash_xof256(inputs: &[impl AsRef<[u8]>], customization: &[u8]) -> Shake256Reader {
+    tuple_hash_xof::<256>(inputs, 0, customization)
+}
@@ -0,0 +1,52 @@
+//! TurboSHAKE extendable output functions, as defined in RFC 9861
+//!
+//! TurboSHAKE is SHAKE with the number of rounds of the permutation
+//! halved to 12, and a configurable domain separation byte.
+
+use std::io::{self, Read};
+
+use crate::{KeccakFlavour, Sponge, SpongeReader, Suffix};
+
+// Keccak Flavour for the 12-round extendable output functions
+#[derive(Debug, Copy, Clone)]
+pub struct TurboShake<const SIZE: usize>;
+
+impl<const SIZE: usize> KeccakFlavour for TurboShake<SIZE> {
+    const CAPACITY: usize = 2 * SIZE;
+    // the default domain separation byte, 0x1f, is the same as SHAKE's suffix
+    const SUFFIX: Suffix = Suffix::S1111;
+    const ROUNDS: usize = 12;
+}
+
+pub type TurboShake128Reader = SpongeReader<TurboShake<128>>;
+pub type TurboShake256Reader = SpongeReader<TurboShake<256>>;
+
+pub(crate) fn turbo_shake<const SIZE: usize>(
+    mut msg: impl Read,
+    domain: u8,
+) -> io::Result<SpongeReader<TurboShake<SIZE>>> {
+    assert!(
+        (0x01..=0x7f).contains(&domain),
+        "TurboSHAKE domain separation byte must be in 0x01..=0x7f"
+    );
+
+    let mut sponge = Sponge::new();
+    io::copy(&mut msg, &mut sponge)?;
+    Ok(sponge.finalize_with_domain(domain))
+}
+
+/// TurboSHAKE128 of `msg` with the domain separation byte `domain`.
+///
+/// # Panics
+/// This function panics if `domain` is not in the `0x01..=0x7f` range.
+pub fn turbo_shake128(msg: impl Read, domain: u8) -> io::Result<TurboShake128Reader> {
+    turbo_shake::<128>(msg, domain)
+}
+
+/// TurboSHAKE256 of `msg` with the domain separation byte `domain`.
+///
+/// # Panics
+/// This function panics if `domain` is not in the `0x01..=0x7f` range.
+pub fn turbo_shake256(msg: impl Read, domain: u8) -> io::Result<TurboShake256Reader> {
+    turbo_shake::<256>(msg, domain)
+}
@@ -0,0 +1,88 @@
+use shake128::batch::{keccak_batch, keccak_p_batch};
+use shake128::optimized::keccak_f1600;
+use shake128::*;
+
+// messages around the block boundaries of both rates
+fn messages() -> Vec<Vec<u8>> {
+    [0, 1, 71, 72, 73, 135, 136, 137, 167, 168, 169, 500, 1000]
+        .iter()
+        .enumerate()
+        .map(|(i, &len)| (0..len).map(|j| (i * 31 + j * 7) as u8).collect())
+        .collect()
+}
+
+fn permutation_matches_scalar<const N: usize>() {
+    let mut lanes = [[0; N]; 25];
+    for (i, lane) in lanes.iter_mut().enumerate() {
+        for (k, word) in lane.iter_mut().enumerate() {
+            *word = (i as u64 + 1).wrapping_mul(0x9e3779b97f4a7c15) ^ k as u64;
+        }
+    }
+
+    let mut expected: [[u64; 25]; N] =
+        std::array::from_fn(|k| std::array::from_fn(|i| lanes[i][k]));
+    expected.iter_mut().for_each(keccak_f1600);
+
+    keccak_p_batch(&mut lanes);
+    for (k, state) in expected.iter().enumerate() {
+        for (i, lane) in state.iter().enumerate() {
+            assert_eq!(lanes[i][k], *lane, "lane {i} of state {k}");
+        }
+    }
+}
+
+#[test]
+fn permutation() {
+    permutation_matches_scalar::<1>();
+    permutation_matches_scalar::<2>();
+    permutation_matches_scalar::<4>();
+    permutation_matches_scalar::<8>();
+}
+
+#[test]
+fn shake_batch() {
+    let msgs = messages();
+    for count in [0, 1, 3, 7, 8, 13] {
+        let refs: Vec<&[u8]> = msgs.iter().take(count).map(Vec::as_slice).collect();
+
+        let outputs = shake128_batch(&refs, 400);
+        assert_eq!(outputs.len(), refs.len());
+        for (msg, output) in refs.iter().zip(&outputs) {
+            let expected: Vec<u8> = shake128(*msg).unwrap().take(400).collect();
+            assert_eq!(*output, expected);
+        }
+
+        for (msg, output) in refs.iter().zip(shake256_batch(&refs, 300)) {
+            let expected: Vec<u8> = shake256(*msg).unwrap().take(300).collect();
+            assert_eq!(output, expected);
+        }
+    }
+}
+
+#[test]
+fn sha3_batch() {
+    let msgs = messages();
+    let refs: Vec<&[u8]> = msgs.iter().map(Vec::as_slice).collect();
+
+    for (msg, digest) in refs.iter().zip(sha3_256_batch(&refs)) {
+        assert_eq!(digest, sha3_256(*msg).unwrap());
+    }
+    for (msg, digest) in refs.iter().zip(sha3_512_batch(&refs)) {
+        assert_eq!(digest, sha3_512(*msg).unwrap());
+    }
+}
+
+#[test]
+fn different_output_lengths() {
+    let msgs = messages();
+    let refs: Vec<&[u8]> = msgs.iter().take(5).map(Vec::as_slice).collect();
+
+    let mut outputs: Vec<Vec<u8>> = [0, 1, 168, 169, 1000].map(|len| vec![0; len]).to_vec();
+    let mut slices: Vec<&mut [u8]> = outputs.iter_mut().map(Vec::as_mut_slice).collect();
+    keccak_batch::<Shake<128>>(&refs, &mut slices);
+
+    for (msg, output) in refs.iter().zip(&outputs) {
+        let expected: Vec<u8> = shake128(*msg).unwrap().take(output.len()).collect();
+        assert_eq!(*output, expected);
+    }
+}
@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+# Downloads the byte-oriented SHA-3 and SHAKE response files from the NIST CAVP
+# and extracts them next to this script
+set -e
+cd "$(dirname "$0")"
+
+URL=https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Algorithm-Validation-Program/documents/sha3
+
+for archive in sha-3bytetestvectors.zip shakebytetestvectors.zip; do
+    curl -fsSLO "$URL/$archive"
+    unzip -joq "$archive" '*.rsp'
+    rm "$archive"
+done
+
+ls *.rsp
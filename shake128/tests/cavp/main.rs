@@ -0,0 +1,200 @@
+//! Runs every vector of the NIST CAVP response files in `tests/cavp`
+//!
+//! The kind of test is taken from the file name, as in the CAVP archives:
+//! `SHA3_256ShortMsg.rsp`, `SHAKE128Monte.rsp`, `SHAKE256VariableOut.rsp`...
+
+mod rsp;
+
+use std::{fmt, fs, path::Path};
+
+use rsp::{parse, Group};
+use shake128::{sha3_224, sha3_256, sha3_384, sha3_512, shake128, shake256};
+
+#[derive(Debug, Clone, Copy)]
+enum Function {
+    Sha3(usize),
+    Shake(usize),
+}
+
+impl Function {
+    fn hash(self, msg: &[u8], len: usize) -> Vec<u8> {
+        let mut output = vec![0; len];
+        match self {
+            Function::Sha3(224) => return sha3_224(msg).unwrap().to_vec(),
+            Function::Sha3(256) => return sha3_256(msg).unwrap().to_vec(),
+            Function::Sha3(384) => return sha3_384(msg).unwrap().to_vec(),
+            Function::Sha3(512) => return sha3_512(msg).unwrap().to_vec(),
+            Function::Shake(128) => shake128(msg).unwrap().squeeze(&mut output),
+            Function::Shake(256) => shake256(msg).unwrap().squeeze(&mut output),
+            _ => unreachable!("unknown function {self:?}"),
+        }
+        output
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    passed: usize,
+    failed: usize,
+    // bit-oriented messages, which the byte-oriented API cannot hash
+    skipped: usize,
+}
+
+impl Counts {
+    fn check(&mut self, ok: bool) {
+        if ok {
+            self.passed += 1;
+        } else {
+            self.failed += 1;
+        }
+    }
+}
+
+impl fmt::Display for Counts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} skipped",
+            self.passed, self.failed, self.skipped
+        )
+    }
+}
+
+// ShortMsg and LongMsg
+fn run_messages(function: Function, groups: &[Group], counts: &mut Counts) {
+    for group in groups {
+        for record in &group.records {
+            if record.num("Len") % 8 != 0 {
+                counts.skipped += 1;
+                continue;
+            }
+
+            let expected = match function {
+                Function::Sha3(_) => record.hex("MD"),
+                Function::Shake(_) => record.hex("Output"),
+            };
+            counts.check(function.hash(&record.msg(), expected.len()) == expected);
+        }
+    }
+}
+
+// SHAKE VariableOut
+fn run_variable_out(function: Function, groups: &[Group], counts: &mut Counts) {
+    for group in groups {
+        for record in &group.records {
+            let len = record.num("Outputlen");
+            if len % 8 != 0 || record.msg().len() * 8 != group.param("Input Length") {
+                counts.skipped += 1;
+                continue;
+            }
+
+            let output = function.hash(&record.msg(), len / 8);
+            counts.check(output == record.hex("Output"));
+        }
+    }
+}
+
+// SHA3VS Monte Carlo test: each checkpoint is 1000 chained hashes
+fn run_sha3_monte(function: Function, groups: &[Group], counts: &mut Counts) {
+    for group in groups {
+        let mut md = Vec::new();
+        for record in &group.records {
+            if record.has("Seed") {
+                md = record.hex("Seed");
+                continue;
+            }
+
+            for _ in 0..1000 {
+                md = function.hash(&md, 0);
+            }
+            counts.check(md == record.hex("MD"));
+
+            // resynchronize after a failure, so that it is only counted once
+            md = record.hex("MD");
+        }
+    }
+}
+
+// SHAKEVS Monte Carlo test: each message is the first 128 bits of the previous
+// output, and each output length is derived from the last 16 bits of the previous output
+fn run_shake_monte(function: Function, groups: &[Group], counts: &mut Counts) {
+    for group in groups {
+        let min = group.param("Minimum Output Length (bits)") / 8;
+        let max = group.param("Maximum Output Length (bits)") / 8;
+        let next_len = |output: &[u8]| {
+            let rightmost =
+                u16::from_be_bytes([output[output.len() - 2], output[output.len() - 1]]);
+            min + rightmost as usize % (max - min + 1)
+        };
+
+        let mut output = Vec::new();
+        let mut len = max;
+        for record in &group.records {
+            if !record.has("COUNT") {
+                output = record.hex("Msg");
+                continue;
+            }
+
+            for _ in 0..1000 {
+                output = function.hash(&output[..16], len);
+                len = next_len(&output);
+            }
+            counts.check(
+                output == record.hex("Output") && output.len() * 8 == record.num("Outputlen"),
+            );
+
+            // resynchronize after a failure, so that it is only counted once
+            output = record.hex("Output");
+            len = next_len(&output);
+        }
+    }
+}
+
+fn run_file(path: &Path) -> Counts {
+    let name = path.file_stem().unwrap().to_str().unwrap();
+    let (function, kind) = if let Some(rest) = name.strip_prefix("SHA3_") {
+        (Function::Sha3(rest[..3].parse().unwrap()), &rest[3..])
+    } else if let Some(rest) = name.strip_prefix("SHAKE") {
+        (Function::Shake(rest[..3].parse().unwrap()), &rest[3..])
+    } else {
+        panic!("unknown response file {name}");
+    };
+
+    let groups = parse(&fs::read_to_string(path).unwrap());
+    let mut counts = Counts::default();
+    match (function, kind) {
+        (_, "ShortMsg" | "LongMsg") => run_messages(function, &groups, &mut counts),
+        (Function::Shake(_), "VariableOut") => run_variable_out(function, &groups, &mut counts),
+        (Function::Sha3(_), "Monte") => run_sha3_monte(function, &groups, &mut counts),
+        (Function::Shake(_), "Monte") => run_shake_monte(function, &groups, &mut counts),
+        _ => panic!("unknown response file {name}"),
+    }
+    counts
+}
+
+#[test]
+fn cavp_response_files() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cavp");
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rsp"))
+        .collect();
+    files.sort();
+    assert!(!files.is_empty(), "no response files found in tests/cavp");
+
+    let mut failed = Vec::new();
+    for path in &files {
+        let counts = run_file(path);
+        let name = path.file_name().unwrap().to_string_lossy();
+        // shown with `cargo test -- --nocapture`, or when a file fails
+        println!("{name:<28} {counts}");
+
+        assert!(counts.passed > 0, "{name}: no vectors were run");
+        if counts.failed > 0 {
+            failed.push(name);
+        }
+    }
+
+    assert!(failed.is_empty(), "failed response files: {failed:?}");
+}
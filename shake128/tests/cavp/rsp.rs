@@ -0,0 +1,221 @@
+//! Parser for NIST CAVP response (`.rsp`) files
+//!
+//! A file is a list of groups, each starting with `[name = value]` parameters,
+//! followed by records of `name = value` lines separated by blank lines.
+//! Lines starting with `#` are comments.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub struct Group {
+    /// bracketed parameters; `[Tested for ...]` lines have an empty value
+    pub params: HashMap<String, String>,
+    pub records: Vec<Record>,
+}
+
+#[derive(Debug, Default)]
+pub struct Record {
+    pub fields: HashMap<String, String>,
+}
+
+fn parse_hex(data: &str) -> Vec<u8> {
+    assert!(
+        data.len().is_multiple_of(2),
+        "odd-length hex string {data:?}"
+    );
+    (0..data.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&data[i..i + 2], 16).expect("invalid hex string"))
+        .collect()
+}
+
+impl Group {
+    pub fn param(&self, name: &str) -> usize {
+        self.params[name].parse().expect("non-numeric parameter")
+    }
+}
+
+impl Record {
+    pub fn has(&self, name: &str) -> bool {
+        self.fields.contains_key(name)
+    }
+
+    pub fn num(&self, name: &str) -> usize {
+        self.fields[name].parse().expect("non-numeric field")
+    }
+
+    pub fn hex(&self, name: &str) -> Vec<u8> {
+        parse_hex(&self.fields[name])
+    }
+
+    /// The message, truncated to `Len` bits when present (empty messages are written "00")
+    pub fn msg(&self) -> Vec<u8> {
+        let mut msg = self.hex("Msg");
+        if self.has("Len") {
+            msg.truncate(self.num("Len").div_ceil(8));
+        }
+        msg
+    }
+}
+
+pub fn parse(data: &str) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+    let mut record = Record::default();
+
+    // a record ends at a blank line, a new group or the end of the file
+    fn flush(groups: &mut Vec<Group>, record: &mut Record) {
+        if !record.fields.is_empty() {
+            if groups.is_empty() {
+                groups.push(Group::default());
+            }
+            groups
+                .last_mut()
+                .unwrap()
+                .records
+                .push(std::mem::take(record));
+        }
+    }
+
+    for line in data.lines().map(str::trim) {
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if line.is_empty() {
+            flush(&mut groups, &mut record);
+        } else if let Some(param) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            flush(&mut groups, &mut record);
+
+            // consecutive parameters belong to the same group
+            if groups.last().is_none_or(|g| !g.records.is_empty()) {
+                groups.push(Group::default());
+            }
+            let (name, value) = param.split_once('=').unwrap_or((param, ""));
+            groups
+                .last_mut()
+                .unwrap()
+                .params
+                .insert(name.trim().into(), value.trim().into());
+        } else {
+            let (name, value) = line
+                .split_once('=')
+                .unwrap_or_else(|| panic!("malformed line {line:?}"));
+            record
+                .fields
+                .insert(name.trim().into(), value.trim().into());
+        }
+    }
+    flush(&mut groups, &mut record);
+
+    groups
+}
+
+#[test]
+fn parse_messages() {
+    let groups = parse(
+        "#  CAVS 19.0\n\
+         #  Length values represented in bits\n\
+         \n\
+         [L = 256]\n\
+         \n\
+         Len = 0\n\
+         Msg = 00\n\
+         MD = a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a\n\
+         \n\
+         Len = 16\n\
+         Msg = 0102\n\
+         MD = ff\n",
+    );
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].param("L"), 256);
+    let records = &groups[0].records;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].num("Len"), 0);
+    assert_eq!(records[0].msg(), b"");
+    assert_eq!(records[0].hex("MD")[..2], [0xa7, 0xff]);
+    assert_eq!(records[1].msg(), [1, 2]);
+    assert_eq!(records[1].hex("MD"), [0xff]);
+}
+
+#[test]
+fn parse_sha3_monte() {
+    let groups = parse(
+        "[L = 224]\n\
+         \n\
+         Seed = 3a9415d4\n\
+         \n\
+         COUNT = 0\n\
+         MD = 90ab\n\
+         \n\
+         COUNT = 1\n\
+         MD = cdef\n",
+    );
+
+    assert_eq!(groups.len(), 1);
+    let records = &groups[0].records;
+    assert_eq!(records.len(), 3);
+    assert!(records[0].has("Seed") && !records[0].has("COUNT"));
+    assert_eq!(records[0].hex("Seed"), [0x3a, 0x94, 0x15, 0xd4]);
+    assert_eq!(records[2].num("COUNT"), 1);
+    assert_eq!(records[2].hex("MD"), [0xcd, 0xef]);
+}
+
+#[test]
+fn parse_shake_monte() {
+    let groups = parse(
+        "[Minimum Output Length (bits) = 128]\n\
+         \n\
+         [Maximum Output Length (bits) = 1120]\n\
+         \n\
+         Msg = c8b3\n\
+         \n\
+         COUNT = 0\n\
+         Outputlen = 16\n\
+         Output = 0f1e\n",
+    );
+
+    // parameters separated by blank lines still make a single group
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].param("Minimum Output Length (bits)"), 128);
+    assert_eq!(groups[0].param("Maximum Output Length (bits)"), 1120);
+    let records = &groups[0].records;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].msg(), [0xc8, 0xb3]);
+    assert_eq!(records[1].num("Outputlen"), 16);
+    assert_eq!(records[1].hex("Output"), [0x0f, 0x1e]);
+}
+
+#[test]
+fn parse_variable_out() {
+    let groups = parse(
+        "[Tested for Output of byte-oriented messages]\n\
+         [Input Length = 128]\n\
+         [Minimum Output Length (bits) = 16]\n\
+         [Maximum Output Length (bits) = 1120]\n\
+         \n\
+         COUNT = 0\n\
+         Outputlen = 16\n\
+         Msg = 00112233445566778899aabbccddeeff\n\
+         Output = ca55\n\
+         \n\
+         [Tested for Output of bit-oriented messages]\n\
+         [Input Length = 128]\n\
+         [Minimum Output Length (bits) = 16]\n\
+         [Maximum Output Length (bits) = 1120]\n\
+         \n\
+         COUNT = 0\n\
+         Outputlen = 17\n\
+         Msg = 00112233445566778899aabbccddeeff\n\
+         Output = ca5580\n",
+    );
+
+    assert_eq!(groups.len(), 2);
+    for group in &groups {
+        assert_eq!(group.param("Input Length"), 128);
+        assert_eq!(group.records.len(), 1);
+        assert_eq!(group.records[0].msg().len(), 16);
+    }
+    assert!(groups[0].params["Tested for Output of byte-oriented messages"].is_empty());
+    assert_eq!(groups[1].records[0].num("Outputlen"), 17);
+}
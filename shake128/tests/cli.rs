@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const BIN: &str = env!("CARGO_BIN_EXE_shake128");
+
+fn run(args: &[&str], dir: &Path) -> Output {
+    Command::new(BIN)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("could not run shake128")
+}
+
+// a scratch directory holding a copy of the test inputs
+fn workdir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("shake128-cli-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("short-text.txt"), include_bytes!("short-text.txt")).unwrap();
+    std::fs::write(
+        dir.join("short-binary.bin"),
+        include_bytes!("short-binary.bin"),
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn print_checksums() {
+    let dir = workdir("print");
+
+    let output = run(&["short-text.txt", "short-binary.bin"], &dir);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "ba27cc6a7a85887a1888c0678c05cd7fcf619ed791dce41b7e1a81c280bec8bb  short-text.txt\n\
+         9b171ccf7ff6b9478ce02a54a5a558dde55febc70e12f0ed402567639e404b74  short-binary.bin\n"
+    );
+
+    let output = run(
+        &[
+            "-a",
+            "shake128",
+            "-l",
+            "4",
+            "-f",
+            "base64",
+            "short-text.txt",
+        ],
+        &dir,
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "uifMag==  short-text.txt\n"
+    );
+
+    let output = run(&["-a", "sha3-256", "-f", "raw", "short-text.txt"], &dir);
+    let expected = shake128::sha3_256(&include_bytes!("short-text.txt")[..]).unwrap();
+    assert_eq!(output.stdout, expected);
+}
+
+#[test]
+fn check_checksums() {
+    let dir = workdir("check");
+
+    for algorithm in [
+        "sha3-224", "sha3-256", "sha3-384", "sha3-512", "shake128", "shake256",
+    ] {
+        for format in ["hex", "base64"] {
+            let sums = run(
+                &[
+                    "-a",
+                    algorithm,
+                    "-f",
+                    format,
+                    "short-text.txt",
+                    "short-binary.bin",
+                ],
+                &dir,
+            );
+            std::fs::write(dir.join("sums"), sums.stdout).unwrap();
+
+            let output = run(&["-a", algorithm, "-f", format, "-c", "sums"], &dir);
+            assert!(output.status.success(), "{algorithm} {format}");
+            assert_eq!(
+                String::from_utf8(output.stdout).unwrap(),
+                "short-text.txt: OK\nshort-binary.bin: OK\n"
+            );
+        }
+    }
+}
+
+#[test]
+fn check_failures() {
+    let dir = workdir("failures");
+
+    let sums = run(
+        &["-a", "sha3-256", "short-text.txt", "short-binary.bin"],
+        &dir,
+    );
+    std::fs::write(dir.join("sums"), sums.stdout).unwrap();
+    std::fs::write(dir.join("short-text.txt"), b"tampered").unwrap();
+
+    let output = run(&["-a", "sha3-256", "-c", "sums"], &dir);
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "short-text.txt: FAILED\nshort-binary.bin: OK\n"
+    );
+
+    std::fs::remove_file(dir.join("short-binary.bin")).unwrap();
+    let output = run(&["-a", "sha3-256", "-c", "sums"], &dir);
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "short-text.txt: FAILED\nshort-binary.bin: FAILED open or read\n"
+    );
+}
+
+#[test]
+fn check_malformed() {
+    let dir = workdir("malformed");
+
+    let sums = run(&["-l", "4", "short-text.txt"], &dir);
+    let line = String::from_utf8(sums.stdout).unwrap();
+    std::fs::write(dir.join("sums"), &line).unwrap();
+
+    // the digest must have the requested length, 32 bytes by default
+    let output = run(&["-l", "4", "-c", "sums"], &dir);
+    assert!(output.status.success());
+    let output = run(&["-c", "sums"], &dir);
+    assert!(!output.status.success());
+    assert_eq!(output.stdout, b"");
+
+    // an empty digest, a signed byte
+    for line in ["  short-text.txt\n", "+1+2+3+4  short-text.txt\n"] {
+        std::fs::write(dir.join("sums"), line).unwrap();
+        let output = run(&["-l", "4", "-c", "sums"], &dir);
+        assert!(!output.status.success(), "{line:?}");
+        assert_eq!(output.stdout, b"");
+        assert!(String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("improperly formatted"));
+    }
+}
+
+#[test]
+fn invalid_options() {
+    let dir = workdir("invalid");
+
+    assert!(!run(&["-a", "md5", "short-text.txt"], &dir).status.success());
+    assert!(!run(&["-a", "sha3-256", "-l", "8", "short-text.txt"], &dir)
+        .status
+        .success());
+    assert!(!run(&["-l", "0", "short-text.txt"], &dir).status.success());
+    assert!(!run(&["-c", "-f", "raw", "short-text.txt"], &dir)
+        .status
+        .success());
+}
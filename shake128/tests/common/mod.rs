@@ -0,0 +1,16 @@
+//! Inputs shared by the test files. Each test crate only uses some of them.
+#![allow(dead_code)]
+
+use io_utils::hex;
+
+// NIST SP 800-185 sample data
+pub const DATA_4: [u8; 4] = hex!("00010203");
+
+pub fn data_200() -> Vec<u8> {
+    (0..200).collect()
+}
+
+// RFC 9861: bytes 00 01 02 ... F9 FA 00 01 ... repeated up to n bytes
+pub fn ptn(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i % 251) as u8).collect()
+}
@@ -0,0 +1,81 @@
+use io_utils::hex;
+use shake128::cshake::{bytepad, encode_string, left_encode, right_encode};
+use shake128::{cshake128, cshake256, shake128};
+
+mod common;
+
+use common::{data_200, DATA_4};
+
+#[test]
+fn encodings() {
+    assert_eq!(left_encode(0), [1, 0]);
+    assert_eq!(left_encode(168), [1, 168]);
+    assert_eq!(left_encode(0x1234), [2, 0x12, 0x34]);
+    assert_eq!(right_encode(0), [0, 1]);
+    assert_eq!(right_encode(256), [1, 0, 2]);
+    assert_eq!(encode_string(b""), [1, 0]);
+    assert_eq!(encode_string(b"ab"), [1, 16, b'a', b'b']);
+
+    let padded = bytepad(b"abc", 8);
+    assert_eq!(padded, [1, 8, b'a', b'b', b'c', 0, 0, 0]);
+}
+
+// NIST SP 800-185 cSHAKE samples
+#[test]
+fn cshake128_sample_1() {
+    let digest = hex!("c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5");
+
+    let output: Vec<u8> = cshake128(b"", b"Email Signature", &DATA_4[..])
+        .unwrap()
+        .take(32)
+        .collect();
+
+    assert_eq!(output, digest);
+}
+
+#[test]
+fn cshake128_sample_2() {
+    let digest = hex!("c5221d50e4f822d96a2e8881a961420f294b7b24fe3d2094baed2c6524cc166b");
+
+    let output: Vec<u8> = cshake128(b"", b"Email Signature", data_200().as_slice())
+        .unwrap()
+        .take(32)
+        .collect();
+
+    assert_eq!(output, digest);
+}
+
+#[test]
+fn cshake256_sample_3() {
+    let digest = hex!("d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd164020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c");
+
+    let output: Vec<u8> = cshake256(b"", b"Email Signature", &DATA_4[..])
+        .unwrap()
+        .take(64)
+        .collect();
+
+    assert_eq!(output, digest);
+}
+
+#[test]
+fn cshake256_sample_4() {
+    let digest = hex!("07dc27b11e51fbac75bc7b3c1d983e8b4b85fb1defaf218912ac86430273091727f42b17ed1df63e8ec118f04b23633c1dfb1574c8fb55cb45da8e25afb092bb");
+
+    let output: Vec<u8> = cshake256(b"", b"Email Signature", data_200().as_slice())
+        .unwrap()
+        .take(64)
+        .collect();
+
+    assert_eq!(output, digest);
+}
+
+#[test]
+fn empty_strings_fall_back_to_shake() {
+    let msg = include_bytes!("short-text.txt");
+
+    assert!(cshake128(b"", b"", msg.as_slice())
+        .unwrap()
+        .zip(shake128(msg.as_slice()).unwrap())
+        .take(512)
+        .all(|(a, b)| a == b));
+}
@@ -0,0 +1,89 @@
+use digest::{Digest, ExtendableOutput, Mac, Update, XofReader};
+use hmac::{Hmac, SimpleHmac};
+use io_utils::hex;
+use shake128::{
+    sha3_256, sha3_512, shake256, Keccak, Sha3_256Hasher, Sha3_512Hasher, Shake256Hasher, Sponge,
+};
+
+const MSG: &[u8] = include_bytes!("short-text.txt");
+
+fn generic_digest<D: Digest>(data: &[u8]) -> Vec<u8> {
+    let mut hasher = D::new();
+    // feed the data in several pieces
+    for chunk in data.chunks(50) {
+        Digest::update(&mut hasher, chunk);
+    }
+    hasher.finalize().to_vec()
+}
+
+fn generic_xof<X: Default + Update + ExtendableOutput>(data: &[u8], len: usize) -> Vec<u8> {
+    let mut hasher = X::default();
+    hasher.update(data);
+    let mut output = vec![0; len];
+    hasher.finalize_xof().read(&mut output);
+    output
+}
+
+#[test]
+fn fixed_size_arrays() {
+    let digest: [u8; 32] = sha3_256(std::io::empty()).unwrap();
+    assert_eq!(
+        digest,
+        hex!("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a")
+    );
+}
+
+#[test]
+fn digest_trait() {
+    assert_eq!(
+        generic_digest::<Sha3_256Hasher>(MSG),
+        sha3_256(MSG).unwrap()
+    );
+    assert_eq!(
+        generic_digest::<Sha3_512Hasher>(MSG),
+        sha3_512(MSG).unwrap()
+    );
+    assert_eq!(
+        generic_digest::<Sha3_256Hasher>(MSG),
+        generic_digest::<sha3::Sha3_256>(MSG)
+    );
+    assert_eq!(
+        generic_digest::<Sponge<Keccak<256>>>(MSG),
+        generic_digest::<sha3::Keccak256>(MSG)
+    );
+}
+
+#[test]
+fn finalize_reset() {
+    let mut hasher = Sha3_256Hasher::new();
+    Digest::update(&mut hasher, b"some unrelated data");
+    Digest::reset(&mut hasher);
+    Digest::update(&mut hasher, MSG);
+
+    let digest = hasher.finalize_reset();
+    assert_eq!(digest[..], sha3_256(MSG).unwrap());
+
+    Digest::update(&mut hasher, MSG);
+    assert_eq!(hasher.finalize()[..], sha3_256(MSG).unwrap());
+}
+
+#[test]
+fn extendable_output_trait() {
+    let output = generic_xof::<Shake256Hasher>(MSG, 300);
+
+    assert_eq!(output, generic_xof::<sha3::Shake256>(MSG, 300));
+    assert!(output.into_iter().eq(shake256(MSG).unwrap().take(300)));
+}
+
+#[test]
+fn hmac() {
+    let key = b"a key that is shorter than a block";
+
+    let mut ours = SimpleHmac::<Sha3_256Hasher>::new_from_slice(key).unwrap();
+    Mac::update(&mut ours, MSG);
+
+    let mut theirs = Hmac::<sha3::Sha3_256>::new_from_slice(key).unwrap();
+    Mac::update(&mut theirs, MSG);
+
+    assert_eq!(ours.finalize().into_bytes(), theirs.finalize().into_bytes());
+}
@@ -0,0 +1,81 @@
+use io_utils::hex;
+use rand::{Rng, RngCore, SeedableRng};
+use shake128::ShakeDrbg;
+
+#[test]
+fn seeded_output() {
+    let mut drbg = ShakeDrbg::new(b"test seed");
+    let mut output = [0; 32];
+    drbg.fill(&mut output);
+
+    assert_eq!(
+        output,
+        hex!("a243e844e829aaba1b4a003aa5fb7c8639539bf278829f20492aca5b46640895")
+    );
+
+    // the rand_core view of the same stream
+    let mut drbg = ShakeDrbg::from_seed([0; 32]);
+    assert_eq!(
+        drbg.next_u64(),
+        u64::from_le_bytes(hex!("83be0ccb2848fcb2"))
+    );
+}
+
+#[test]
+fn ratchet_and_reseed() {
+    // both start from the 64 bytes following the state
+    let mut drbg = ShakeDrbg::new(b"test seed");
+    drbg.ratchet();
+    let mut output = [0; 32];
+    drbg.fill(&mut output);
+    assert_eq!(
+        output,
+        hex!("143251d2ba16641f0ca2cadb1e645ed65d99af17e23903d10e5c3f05ee1b6d4d")
+    );
+
+    let mut drbg = ShakeDrbg::new(b"test seed");
+    drbg.reseed(b"entropy");
+    drbg.fill(&mut output);
+    assert_eq!(
+        output,
+        hex!("6e49b0e60107f66e015dd1ce92e799e92480fc3e07a9cb81323b01d642009ec6")
+    );
+}
+
+#[test]
+fn reproducible() {
+    let mut a = ShakeDrbg::seed_from_u64(7);
+    let mut b = a.clone();
+
+    let x: [u8; 32] = a.gen();
+    let y: [u8; 32] = b.gen();
+    assert_eq!(x, y);
+
+    a.reseed(b"more entropy");
+    assert_ne!(a.gen::<u128>(), b.gen::<u128>());
+}
+
+#[test]
+fn split_fills() {
+    // output does not depend on how it is requested
+    let mut a = ShakeDrbg::new(b"");
+    let mut b = ShakeDrbg::new(b"");
+
+    let mut whole = [0; 1000];
+    a.fill_bytes(&mut whole);
+
+    let mut parts = [0; 1000];
+    for chunk in parts.chunks_mut(77) {
+        b.fill_bytes(chunk);
+    }
+
+    assert_eq!(whole, parts);
+}
+
+#[test]
+fn debug_hides_state() {
+    assert_eq!(
+        format!("{:?}", ShakeDrbg::new(b"secret")),
+        "ShakeDrbg { .. }"
+    );
+}
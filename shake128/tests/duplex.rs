@@ -0,0 +1,121 @@
+use shake128::sponge_wrap::{open, seal};
+use shake128::{keccak, Duplex128, Duplex256, Keccak, KeccakFlavour};
+
+const KEY: [u8; 32] = [0x42; 32];
+const NONCE: [u8; 16] = [0x24; 16];
+
+// a duplex call outputs the same as a sponge absorbing the padded previous
+// inputs, followed by the current input
+fn padded<F: KeccakFlavour>(inputs: &[&[u8]]) -> Vec<u8> {
+    let rate = (1600 - F::CAPACITY) / 8;
+    let mut msg = Vec::new();
+    for input in inputs {
+        let mut block = vec![0; rate];
+        block[..input.len()].copy_from_slice(input);
+        block[input.len()] ^= 0x01;
+        block[rate - 1] ^= 0x80;
+        msg.extend_from_slice(&block);
+    }
+    msg
+}
+
+#[test]
+fn duplex_matches_sponge() {
+    let inputs: [&[u8]; 4] = [b"", b"abc", &[0x5a; 167], &[0xa5; 100]];
+
+    let mut duplex = Duplex128::new();
+    for (i, input) in inputs.iter().enumerate() {
+        let mut msg = padded::<Keccak<128>>(&inputs[..i]);
+        msg.extend_from_slice(input);
+        let expected: Vec<u8> = keccak::<Keccak<128>>(msg.as_slice())
+            .unwrap()
+            .take(Duplex128::RATE)
+            .collect();
+
+        assert_eq!(duplex.duplex(input), expected);
+    }
+
+    let mut duplex = Duplex256::new();
+    let expected: Vec<u8> = keccak::<Keccak<256>>(&b"abc"[..])
+        .unwrap()
+        .take(Duplex256::RATE)
+        .collect();
+    assert_eq!(duplex.duplex(b"abc"), expected);
+}
+
+#[test]
+#[should_panic]
+fn duplex_input_too_long() {
+    Duplex128::new().duplex(&[0; 168]);
+}
+
+#[test]
+fn seal_open() {
+    for ad_len in [0, 1, 166, 167, 168, 400] {
+        for msg_len in [0, 1, 166, 167, 168, 334, 1000] {
+            let ad = vec![0x11; ad_len];
+            let msg: Vec<u8> = (0..msg_len).map(|i| i as u8).collect();
+
+            let mut ciphertext = Vec::new();
+            let tag = seal(&KEY, &NONCE, ad.as_slice(), msg.as_slice(), &mut ciphertext).unwrap();
+            assert_eq!(ciphertext.len(), msg.len());
+            assert!(msg.is_empty() || ciphertext != msg);
+
+            let mut plaintext = Vec::new();
+            let valid = open(
+                &KEY,
+                &NONCE,
+                ad.as_slice(),
+                ciphertext.as_slice(),
+                &tag,
+                &mut plaintext,
+            )
+            .unwrap();
+            assert!(valid, "ad: {ad_len}, msg: {msg_len}");
+            assert_eq!(plaintext, msg);
+        }
+    }
+}
+
+#[test]
+fn tampering() {
+    let ad = b"header";
+    let msg = vec![0x33; 500];
+
+    let mut ciphertext = Vec::new();
+    let tag = seal(&KEY, &NONCE, &ad[..], msg.as_slice(), &mut ciphertext).unwrap();
+
+    let check = |key: &[u8; 32], nonce: &[u8; 16], ad: &[u8], ciphertext: &[u8], tag: &[u8; 16]| {
+        open(key, nonce, ad, ciphertext, tag, std::io::sink()).unwrap()
+    };
+    assert!(check(&KEY, &NONCE, ad, &ciphertext, &tag));
+
+    let mut bad_key = KEY;
+    bad_key[0] ^= 1;
+    assert!(!check(&bad_key, &NONCE, ad, &ciphertext, &tag));
+
+    let mut bad_nonce = NONCE;
+    bad_nonce[15] ^= 1;
+    assert!(!check(&KEY, &bad_nonce, ad, &ciphertext, &tag));
+
+    assert!(!check(&KEY, &NONCE, b"headex", &ciphertext, &tag));
+
+    let mut bad_ciphertext = ciphertext.clone();
+    bad_ciphertext[300] ^= 0x80;
+    assert!(!check(&KEY, &NONCE, ad, &bad_ciphertext, &tag));
+    assert!(!check(&KEY, &NONCE, ad, &ciphertext[..499], &tag));
+
+    let mut bad_tag = tag;
+    bad_tag[7] ^= 4;
+    assert!(!check(&KEY, &NONCE, ad, &ciphertext, &bad_tag));
+}
+
+#[test]
+fn framing() {
+    // moving bytes between the associated data and the message changes the tag
+    let seal = |ad: &[u8], msg: &[u8]| seal(&KEY, &NONCE, ad, msg, std::io::sink()).unwrap();
+
+    assert_ne!(seal(b"a", b"bc"), seal(b"ab", b"c"));
+    assert_ne!(seal(b"", b"abc"), seal(b"abc", b""));
+    assert_ne!(seal(&[0; 167], b""), seal(&[0; 167], &[0; 167]));
+}
@@ -0,0 +1,123 @@
+use io_utils::hex;
+use shake128::kangaroo_twelve::length_encode;
+use shake128::{kt128, kt256};
+
+mod common;
+
+use common::ptn;
+
+// RFC 9861 test vectors
+
+fn kt128_32(msg: &[u8], customization: &[u8]) -> [u8; 32] {
+    let mut output = [0; 32];
+    kt128(msg, customization).unwrap().squeeze(&mut output);
+    output
+}
+
+#[test]
+fn length_encoding() {
+    assert_eq!(length_encode(0), [0]);
+    assert_eq!(length_encode(12), [12, 1]);
+    assert_eq!(length_encode(65538), [1, 0, 2, 3]);
+}
+
+#[test]
+fn kt128_empty() {
+    let digest = hex!("1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5");
+    assert_eq!(kt128_32(b"", b""), digest);
+
+    let digest = hex!("e8dc563642f7228c84684c898405d3a834799158c079b12880277a1d28e2ff6d");
+    let mut output = vec![0; 10032];
+    kt128(std::io::empty(), b"").unwrap().squeeze(&mut output);
+    assert_eq!(output[10000..], digest);
+}
+
+#[test]
+fn kt128_messages() {
+    // lengths 17^0 to 17^5 go from a single node up to ten leaves
+    let digests = [
+        hex!("2bda92450e8b147f8a7cb629e784a058efca7cf7d8218e02d345dfaa65244a1f"),
+        hex!("6bf75fa2239198db4772e36478f8e19b0f371205f6a9a93a273f51df37122888"),
+        hex!("0c315ebcdedbf61426de7dcf8fb725d1e74675d7f5327a5067f367b108ecb67c"),
+        hex!("cb552e2ec77d9910701d578b457ddf772c12e322e4ee7fe417f92c758f0d59d0"),
+        hex!("8701045e22205345ff4dda05555cbb5c3af1a771c2b89baef37db43d9998b9fe"),
+        hex!("844d610933b1b9963cbdeb5ae3b6b05cc7cbd67ceedf883eb678a0a8e0371682"),
+    ];
+
+    for (e, digest) in digests.into_iter().enumerate() {
+        assert_eq!(kt128_32(&ptn(17usize.pow(e as u32)), b""), digest, "17^{e}");
+    }
+}
+
+#[test]
+fn kt128_customization() {
+    let samples = [
+        (
+            0,
+            0,
+            hex!("fab658db63e94a246188bf7af69a133045f46ee984c56e3c3328caaf1aa1a583"),
+        ),
+        (
+            1,
+            1,
+            hex!("d848c5068ced736f4462159b9867fd4c20b808acc3d5bc48e0b06ba0a3762ec4"),
+        ),
+        (
+            3,
+            2,
+            hex!("c389e5009ae57120854c2e8c64670ac01358cf4c1baf89447a724234dc7ced74"),
+        ),
+        (
+            7,
+            3,
+            hex!("75d2f86a2e644566726b4fbcfc5657b9dbcf070c7b0dca06450ab291d7443bcf"),
+        ),
+    ];
+
+    for (len, e, digest) in samples {
+        assert_eq!(
+            kt128_32(&vec![0xff; len], &ptn(41usize.pow(e))),
+            digest,
+            "customization 41^{e}"
+        );
+    }
+}
+
+#[test]
+fn kt128_chunk_boundary() {
+    let samples = [
+        (
+            8191,
+            0,
+            hex!("1b577636f723643e990cc7d6a659837436fd6a103626600eb8301cd1dbe553d6"),
+        ),
+        (
+            8192,
+            0,
+            hex!("48f256f6772f9edfb6a8b661ec92dc93b95ebd05a08a17b39ae3490870c926c3"),
+        ),
+        (
+            8192,
+            8189,
+            hex!("3ed12f70fb05ddb58689510ab3e4d23c6c6033849aa01e1d8c220a297fedcd0b"),
+        ),
+        (
+            8192,
+            8190,
+            hex!("6a7c1b6a5cd0d8c9ca943a4a216cc64604559a2ea45f78570a15253d67ba00ae"),
+        ),
+    ];
+
+    for (len, custom_len, digest) in samples {
+        assert_eq!(kt128_32(&ptn(len), &ptn(custom_len)), digest);
+    }
+}
+
+#[test]
+fn kt256_empty() {
+    let digest = hex!("b23d2e9cea9f4904e02bec06817fc10ce38ce8e93ef4c89e6537076af8646404e3e8b68107b8833a5d30490aa33482353fd4adc7148ecb782855003aaebde4a9");
+
+    let mut output = [0; 64];
+    kt256(std::io::empty(), b"").unwrap().squeeze(&mut output);
+    assert_eq!(output, digest);
+}
@@ -0,0 +1,89 @@
+use io_utils::hex;
+use shake128::{
+    keccak, keccak224, keccak256, keccak384, keccak512, shake128, KeccakFlavour, Suffix,
+};
+
+// original Keccak submission padding (as used by Ethereum)
+
+#[test]
+fn empty_message() {
+    assert_eq!(
+        keccak224(std::io::empty()).unwrap(),
+        hex!("f71837502ba8e10837bdd8d365adb85591895602fc552b48b7390abd")
+    );
+    assert_eq!(
+        keccak256(std::io::empty()).unwrap(),
+        hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+    );
+    assert_eq!(
+        keccak384(std::io::empty()).unwrap(),
+        hex!("2c23146a63a29acf99e73b88f8c24eaa7dc60aa771780ccc006afbfa8fe2479b2dd2b21362337441ac12b515911957ff")
+    );
+    assert_eq!(
+        keccak512(std::io::empty()).unwrap(),
+        hex!("0eab42de4c3ceb9235fc91acffe746b29c29a8c366b7c60e4e67c466f36a4304c00fa9caf9d87976ba469bcbe06713b435f091ef2769fb160cdab33d3670680e")
+    );
+}
+
+#[test]
+fn keccak256_vectors() {
+    let samples: [(&[u8], [u8; 32]); 3] = [
+        (
+            b"abc",
+            hex!("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"),
+        ),
+        (
+            b"The quick brown fox jumps over the lazy dog",
+            hex!("4d741b6f1eb29cb2a9b9911c82f56fa8d73b04959d3d9d222895df6c0b28aa15"),
+        ),
+        (
+            &std::array::from_fn::<u8, 200, _>(|i| i as u8),
+            hex!("bfb0aa97863e797943cf7c33bb7e880bb4543f3d2703c0923c6901c2af57b890"),
+        ),
+    ];
+
+    for (msg, digest) in samples {
+        assert_eq!(keccak256(msg).unwrap(), digest);
+    }
+}
+
+#[test]
+fn keccak512_multiblock() {
+    let msg: [u8; 200] = std::array::from_fn(|i| i as u8);
+    let digest = hex!("f452d81b62b961f8023f8228cbe780379b36c49ddcef29e0dffb01a930c2cc53a694ed6ae3f0d224a2f1be55814a81841b90d56bcdf4a48a633f258a32dc14fc");
+
+    assert_eq!(keccak512(&msg[..]).unwrap(), digest);
+}
+
+#[test]
+fn suffix_bits() {
+    assert_eq!(Suffix::NONE.domain_byte(), 0x01);
+    assert_eq!(Suffix::S00.domain_byte(), 0x04);
+    assert_eq!(Suffix::S01.domain_byte(), 0x06);
+    assert_eq!(Suffix::S1111.domain_byte(), 0x1f);
+    assert_eq!(Suffix::new(0b101_1010, 7).domain_byte(), 0xda);
+}
+
+#[test]
+#[should_panic]
+fn suffix_too_long() {
+    Suffix::new(0, 8);
+}
+
+#[test]
+fn custom_flavour() {
+    // a user-defined flavour with the same parameters as SHAKE128
+    struct Custom;
+
+    impl KeccakFlavour for Custom {
+        const CAPACITY: usize = 256;
+        const SUFFIX: Suffix = Suffix::new(0b1111, 4);
+    }
+
+    let msg = include_bytes!("short-text.txt");
+    assert!(keccak::<Custom>(msg.as_slice())
+        .unwrap()
+        .zip(shake128(msg.as_slice()).unwrap())
+        .take(400)
+        .all(|(a, b)| a == b));
+}
@@ -0,0 +1,137 @@
+use io_utils::hex;
+use shake128::{kmac, kmac128, kmac256, kmac_xof128, kmac_xof256};
+
+mod common;
+
+use common::{data_200, DATA_4};
+
+// NIST SP 800-185 KMAC samples
+const KEY: [u8; 32] = hex!("404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f");
+const TAGGED: &[u8] = b"My Tagged Application";
+
+#[test]
+fn kmac128_samples() {
+    let samples: [(&[u8], &[u8], [u8; 32]); 3] = [
+        (
+            &DATA_4,
+            b"",
+            hex!("e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e"),
+        ),
+        (
+            &DATA_4,
+            TAGGED,
+            hex!("3b1fba963cd8b0b59e8c1a6d71888b7143651af8ba0a7070c0979e2811324aa5"),
+        ),
+        (
+            &data_200(),
+            TAGGED,
+            hex!("1f5b4e6cca02209e0dcb5ca635b89a15e271ecc760071dfd805faa38f9729230"),
+        ),
+    ];
+
+    for (data, customization, tag) in samples {
+        assert_eq!(kmac128(&KEY, data, 32, customization).unwrap(), tag);
+    }
+}
+
+#[test]
+fn kmac256_samples() {
+    let samples: [(&[u8], &[u8], [u8; 64]); 3] = [
+        (
+            &DATA_4,
+            TAGGED,
+            hex!("20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd"),
+        ),
+        (
+            &data_200(),
+            b"",
+            hex!("75358cf39e41494e949707927cee0af20a3ff553904c86b08f21cc414bcfd691589d27cf5e15369cbbff8b9a4c2eb17800855d0235ff635da82533ec6b759b69"),
+        ),
+        (
+            &data_200(),
+            TAGGED,
+            hex!("b58618f71f92e1d56c1b8c55ddd7cd188b97b4ca4d99831eb2699a837da2e4d970fbacfde50033aea585f1a2708510c32d07880801bd182898fe476876fc8965"),
+        ),
+    ];
+
+    for (data, customization, tag) in samples {
+        assert_eq!(kmac256(&KEY, data, 64, customization).unwrap(), tag);
+    }
+}
+
+#[test]
+fn kmac_xof128_samples() {
+    let samples: [(&[u8], &[u8], [u8; 32]); 3] = [
+        (
+            &DATA_4,
+            b"",
+            hex!("cd83740bbd92ccc8cf032b1481a0f4460e7ca9dd12b08a0c4031178bacd6ec35"),
+        ),
+        (
+            &DATA_4,
+            TAGGED,
+            hex!("31a44527b4ed9f5c6101d11de6d26f0620aa5c341def41299657fe9df1a3b16c"),
+        ),
+        (
+            &data_200(),
+            TAGGED,
+            hex!("47026c7cd793084aa0283c253ef658490c0db61438b8326fe9bddf281b83ae0f"),
+        ),
+    ];
+
+    for (data, customization, tag) in samples {
+        let output: Vec<u8> = kmac_xof128(&KEY, data, customization)
+            .unwrap()
+            .take(32)
+            .collect();
+        assert_eq!(output, tag);
+    }
+}
+
+#[test]
+fn kmac_xof256_samples() {
+    let samples: [(&[u8], &[u8], [u8; 64]); 3] = [
+        (
+            &DATA_4,
+            TAGGED,
+            hex!("1755133f1534752aad0748f2c706fb5c784512cab835cd15676b16c0c6647fa96faa7af634a0bf8ff6df39374fa00fad9a39e322a7c92065a64eb1fb0801eb2b"),
+        ),
+        (
+            &data_200(),
+            b"",
+            hex!("ff7b171f1e8a2b24683eed37830ee797538ba8dc563f6da1e667391a75edc02ca633079f81ce12a25f45615ec89972031d18337331d24ceb8f8ca8e6a19fd98b"),
+        ),
+        (
+            &data_200(),
+            TAGGED,
+            hex!("d5be731c954ed7732846bb59dbe3a8e30f83e77a4bff4459f2f1c2b4ecebb8ce67ba01c62e8ab8578d2d499bd1bb276768781190020a306a97de281dcc30305d"),
+        ),
+    ];
+
+    for (data, customization, tag) in samples {
+        let output: Vec<u8> = kmac_xof256(&KEY, data, customization)
+            .unwrap()
+            .take(64)
+            .collect();
+        assert_eq!(output, tag);
+    }
+}
+
+#[test]
+fn verify() {
+    let tag = hex!("3b1fba963cd8b0b59e8c1a6d71888b7143651af8ba0a7070c0979e2811324aa5");
+    assert!(kmac::verify128(&KEY, &DATA_4[..], TAGGED, 32, &tag).unwrap());
+
+    let mut bad_tag = tag;
+    bad_tag[31] ^= 1;
+    assert!(!kmac::verify128(&KEY, &DATA_4[..], TAGGED, 32, &bad_tag).unwrap());
+
+    // only tags of the expected length are accepted
+    assert!(!kmac::verify128(&KEY, &DATA_4[..], TAGGED, 32, &tag[..16]).unwrap());
+    assert!(!kmac::verify128(&KEY, &DATA_4[..], TAGGED, 32, &tag[..1]).unwrap());
+    assert!(!kmac::verify128(&KEY, &DATA_4[..], TAGGED, 32, &[]).unwrap());
+    assert!(kmac::verify128(&KEY, &DATA_4[..], TAGGED, 0, &[]).is_err());
+
+    // a truncated tag is a different MAC, not a prefix
+    assert!(!kmac::verify128(&KEY, &DATA_4[..], TAGGED, 16, &tag[..16]).unwrap());
+}
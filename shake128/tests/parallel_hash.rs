@@ -0,0 +1,92 @@
+use io_utils::hex;
+use shake128::{parallel_hash128, parallel_hash256, parallel_hash_xof128, parallel_hash_xof256};
+
+// NIST SP 800-185 ParallelHash samples
+const X_24: [u8; 24] = hex!("000102030405060710111213141516172021222324252627");
+const X_48: [u8; 48] = hex!("000102030405060710111213141516172021222324252627303132333435363740414243444546475051525354555657");
+const PARALLEL_DATA: &[u8] = b"Parallel Data";
+
+#[test]
+fn parallel_hash128_samples() {
+    let samples: [(&[u8], &[u8], [u8; 32]); 3] = [
+        (
+            &X_24,
+            b"",
+            hex!("ba8dc1d1d979331d3f813603c67f72609ab5e44b94a0b8f9af46514454a2b4f5"),
+        ),
+        (
+            &X_24,
+            PARALLEL_DATA,
+            hex!("fc484dcb3f84dceedc353438151bee58157d6efed0445a81f165e495795b7206"),
+        ),
+        (
+            &X_48,
+            PARALLEL_DATA,
+            hex!("9d2fd090804d4123baae7f222c938fa183dc7316019c5f2938c6630587d213ba"),
+        ),
+    ];
+
+    for (data, customization, digest) in samples {
+        assert_eq!(
+            parallel_hash128(data, 8, 32, customization).unwrap(),
+            digest
+        );
+    }
+}
+
+#[test]
+fn parallel_hash256_samples() {
+    let samples: [(&[u8], &[u8], [u8; 64]); 3] = [
+        (
+            &X_24,
+            b"",
+            hex!("bc1ef124da34495e948ead207dd9842235da432d2bbc54b4c110e64c451105531b7f2a3e0ce055c02805e7c2de1fb746af97a1dd01f43b824e31b87612410429"),
+        ),
+        (
+            &X_24,
+            PARALLEL_DATA,
+            hex!("cdf15289b54f6212b4bc270528b49526006dd9b54e2b6add1ef6900dda3963bb33a72491f236969ca8afaea29c682d47a393c065b38e29fae651a2091c833110"),
+        ),
+        (
+            &X_48,
+            PARALLEL_DATA,
+            hex!("6f34ff83647ffdaff56e1307ce1037df349a3be62653038249214ad6f6197c492480fecbec6bbefd7b7a1b2ad321e599e16dc7ab81ca683f227ce62d67495277"),
+        ),
+    ];
+
+    for (data, customization, digest) in samples {
+        assert_eq!(
+            parallel_hash256(data, 8, 64, customization).unwrap(),
+            digest
+        );
+    }
+}
+
+#[test]
+fn parallel_hash_xof_samples() {
+    let digest = hex!("66c26967ef682b0fc88df91d93daeb14474654494072423de18a9832a2690d8a");
+    let output: Vec<u8> = parallel_hash_xof128(&X_48[..], 8, PARALLEL_DATA)
+        .unwrap()
+        .take(32)
+        .collect();
+    assert_eq!(output, digest);
+
+    let digest = hex!("c10a052722614684144d28474850b410757e3cba87651ba167a5cbddff7f466675fbf84bcae7378ac444be681d729499afca667fb879348bfdda427863c82f1c");
+    let output: Vec<u8> = parallel_hash_xof256(&X_24[..], 8, b"")
+        .unwrap()
+        .take(64)
+        .collect();
+    assert_eq!(output, digest);
+}
+
+#[test]
+fn many_blocks() {
+    // spans several blocks per worker, with a partial final block
+    let data: Vec<u8> = (0..100000u32).map(|i| (i * 7 + 3) as u8).collect();
+    let digest = hex!("a0d6d3f00b33cb7c1ea08586bc40c88a63bab3d5f13e7aa92cd2f58249ee452f");
+
+    assert_eq!(
+        parallel_hash128(data.as_slice(), 1024, 32, b"big").unwrap(),
+        digest
+    );
+}
@@ -385,3 +385,54 @@ fn after_keccak_p() {
 
     assert_eq!(&*data, &after);
 }
+
+#[test]
+fn reference_keccak_p() {
+    let mut data = AlignedData([0; 200]);
+    keccak_p_reference(&mut data);
+
+    assert_eq!(*data, DATA);
+}
+
+#[test]
+fn optimized_matches_reference() {
+    // start from the test vectors, and keep permuting to cover many unrelated states
+    for start in [[0; 200], DATA] {
+        let mut optimized = AlignedData(start);
+        let mut reference = AlignedData(start);
+
+        for i in 0..256 {
+            keccak_p(&mut optimized);
+            keccak_p_reference(&mut reference);
+            assert_eq!(
+                optimized,
+                reference,
+                "diverged after {} permutations",
+                i + 1
+            );
+        }
+    }
+}
+
+#[test]
+fn optimized_round_reduced() {
+    // Keccak-p[1600, 12] runs the last 12 rounds of Keccak-f[1600]
+    let mut optimized = AlignedData(DATA);
+    keccak_p12(&mut optimized);
+
+    let mut reference = State::from(DATA);
+    for index in 12..24 {
+        round(&mut reference, index);
+    }
+    assert_eq!(State::from(*optimized), reference);
+
+    // odd number of rounds
+    let mut optimized = AlignedData(DATA);
+    keccak_p_rounds(&mut optimized, 5);
+
+    let mut reference = State::from(DATA);
+    for index in 19..24 {
+        round(&mut reference, index);
+    }
+    assert_eq!(State::from(*optimized), reference);
+}
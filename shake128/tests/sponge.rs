@@ -0,0 +1,120 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use io_utils::hex;
+use shake128::{sha3_256, shake128, Sha3_256Hasher, Shake128Hasher};
+
+const MSG: &[u8] = include_bytes!("short-binary.bin");
+
+#[test]
+fn empty_message() {
+    let digest = hex!("7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26");
+
+    let mut output = [0; 32];
+    Shake128Hasher::new().finalize_xof().squeeze(&mut output);
+
+    assert_eq!(output, digest);
+}
+
+#[test]
+fn piecewise_update() {
+    let mut expected = [0; 1000];
+    for (e, b) in expected.iter_mut().zip(shake128(MSG).unwrap()) {
+        *e = b;
+    }
+
+    // uneven pieces, straddling rate boundaries
+    for piece in [1, 7, 167, 168, 169, 500] {
+        let mut hasher = Shake128Hasher::new();
+        for chunk in MSG.chunks(piece) {
+            hasher.update(chunk);
+        }
+
+        let mut output = [0; 1000];
+        hasher.finalize_xof().squeeze(&mut output);
+        assert_eq!(output, expected, "pieces of {piece} bytes");
+    }
+}
+
+#[test]
+fn bulk_squeeze() {
+    let mut reader = Shake128Hasher::new().finalize_xof();
+    let expected: Vec<u8> = reader.clone().take(1000).collect();
+
+    let mut output = vec![0; 1000];
+    let (a, b) = output.split_at_mut(13);
+    reader.squeeze(a);
+    reader.squeeze(b);
+
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn fork_midstate() {
+    let mut prefix = Sha3_256Hasher::new();
+    prefix.update(b"common prefix, ");
+
+    let mut a = prefix.clone();
+    a.update(b"first suffix");
+    let mut b = prefix;
+    b.update(b"second suffix");
+
+    let mut digest_a = [0; 32];
+    a.finalize_xof().squeeze(&mut digest_a);
+    let mut digest_b = [0; 32];
+    b.finalize_xof().squeeze(&mut digest_b);
+
+    assert_eq!(
+        digest_a,
+        sha3_256(&b"common prefix, first suffix"[..]).unwrap()
+    );
+    assert_eq!(
+        digest_b,
+        sha3_256(&b"common prefix, second suffix"[..]).unwrap()
+    );
+}
+
+#[test]
+fn seekable_reader() {
+    let mut expected = vec![0; 2000];
+    shake128(MSG).unwrap().squeeze(&mut expected);
+
+    let mut reader = shake128(MSG).unwrap().seekable();
+    let mut buf = [0; 100];
+
+    // forward, across block boundaries, then backward
+    for start in [0, 150, 168, 500, 1337, 3, 167, 1000, 0] {
+        assert_eq!(reader.seek(SeekFrom::Start(start)).unwrap(), start);
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf[..], expected[start as usize..start as usize + 100]);
+        assert_eq!(reader.position(), start + 100);
+    }
+
+    reader.seek(SeekFrom::Start(1000)).unwrap();
+    assert_eq!(reader.seek(SeekFrom::Current(-400)).unwrap(), 600);
+    assert_eq!(reader.seek(SeekFrom::Current(50)).unwrap(), 650);
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[..], expected[650..750]);
+
+    assert!(reader.seek(SeekFrom::Current(-751)).is_err());
+    assert!(reader.seek(SeekFrom::End(0)).is_err());
+    assert_eq!(reader.stream_position().unwrap(), 750);
+}
+
+#[test]
+fn seekable_mid_stream() {
+    let mut expected = vec![0; 1000];
+    shake128(MSG).unwrap().squeeze(&mut expected);
+
+    // positions are relative to where the reader was
+    let mut reader = shake128(MSG).unwrap();
+    reader.squeeze(&mut [0; 200]);
+    let mut reader = reader.seekable();
+
+    let mut buf = vec![0; 800];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, expected[200..]);
+
+    reader.seek(SeekFrom::Start(10)).unwrap();
+    reader.read_exact(&mut buf[..100]).unwrap();
+    assert_eq!(buf[..100], expected[210..310]);
+}
@@ -0,0 +1,79 @@
+use io_utils::hex;
+use shake128::{tuple_hash128, tuple_hash256, tuple_hash_xof128, tuple_hash_xof256};
+
+// NIST SP 800-185 TupleHash samples
+const A: &[u8] = &hex!("000102");
+const B: &[u8] = &hex!("101112131415");
+const C: &[u8] = &hex!("202122232425262728");
+const TUPLE_APP: &[u8] = b"My Tuple App";
+
+// (tuple, customization string, digest)
+type Sample<const N: usize> = (&'static [&'static [u8]], &'static [u8], [u8; N]);
+
+#[test]
+fn tuple_hash128_samples() {
+    let samples: [Sample<32>; 3] = [
+        (
+            &[A, B],
+            b"",
+            hex!("c5d8786c1afb9b82111ab34b65b2c0048fa64e6d48e263264ce1707d3ffc8ed1"),
+        ),
+        (
+            &[A, B],
+            TUPLE_APP,
+            hex!("75cdb20ff4db1154e841d758e24160c54bae86eb8c13e7f5f40eb35588e96dfb"),
+        ),
+        (
+            &[A, B, C],
+            TUPLE_APP,
+            hex!("e60f202c89a2631eda8d4c588ca5fd07f39e5151998deccf973adb3804bb6e84"),
+        ),
+    ];
+
+    for (inputs, customization, digest) in samples {
+        assert_eq!(tuple_hash128(inputs, 32, customization), digest);
+    }
+}
+
+#[test]
+fn tuple_hash256_samples() {
+    let samples: [Sample<64>; 3] = [
+        (
+            &[A, B],
+            b"",
+            hex!("cfb7058caca5e668f81a12a20a2195ce97a925f1dba3e7449a56f82201ec607311ac2696b1ab5ea2352df1423bde7bd4bb78c9aed1a853c78672f9eb23bbe194"),
+        ),
+        (
+            &[A, B],
+            TUPLE_APP,
+            hex!("147c2191d5ed7efd98dbd96d7ab5a11692576f5fe2a5065f3e33de6bba9f3aa1c4e9a068a289c61c95aab30aee1e410b0b607de3620e24a4e3bf9852a1d4367e"),
+        ),
+        (
+            &[A, B, C],
+            TUPLE_APP,
+            hex!("45000be63f9b6bfd89f54717670f69a9bc763591a4f05c50d68891a744bcc6e7d6d5b5e82c018da999ed35b0bb49c9678e526abd8e85c13ed254021db9e790ce"),
+        ),
+    ];
+
+    for (inputs, customization, digest) in samples {
+        assert_eq!(tuple_hash256(inputs, 64, customization), digest);
+    }
+}
+
+#[test]
+fn tuple_hash_xof_samples() {
+    let digest = hex!("900fe16cad098d28e74d632ed852f99daab7f7df4d99e775657885b4bf76d6f8");
+    let output: Vec<u8> = tuple_hash_xof128(&[A, B, C], TUPLE_APP).take(32).collect();
+    assert_eq!(output, digest);
+
+    let digest = hex!("03ded4610ed6450a1e3f8bc44951d14fbc384ab0efe57b000df6b6df5aae7cd568e77377daf13f37ec75cf5fc598b6841d51dd207c991cd45d210ba60ac52eb9");
+    let output: Vec<u8> = tuple_hash_xof256(&[A, B], b"").take(64).collect();
+    assert_eq!(output, digest);
+}
+
+#[test]
+fn unambiguous() {
+    let split1 = tuple_hash128(&[&b"ab"[..], b"c"], 32, b"");
+    let split2 = tuple_hash128(&[&b"a"[..], b"bc"], 32, b"");
+    assert_ne!(split1, split2);
+}
@@ -0,0 +1,109 @@
+use io_utils::hex;
+use shake128::{turbo_shake128, turbo_shake256};
+
+mod common;
+
+use common::ptn;
+
+// RFC 9861 test vectors
+
+#[test]
+fn turbo_shake128_vectors() {
+    let samples: [(Vec<u8>, u8, [u8; 32]); 8] = [
+        (
+            vec![],
+            0x1f,
+            hex!("1e415f1c5983aff2169217277d17bb538cd945a397ddec541f1ce41af2c1b74c"),
+        ),
+        (
+            ptn(17),
+            0x1f,
+            hex!("9c97d036a3bac819db70ede0ca554ec6e4c2a1a4ffbfd9ec269ca6a111161233"),
+        ),
+        (
+            ptn(17 * 17),
+            0x1f,
+            hex!("96c77c279e0126f7fc07c9b07f5cdae1e0be60bdbe10620040e75d7223a624d2"),
+        ),
+        (
+            ptn(17 * 17 * 17),
+            0x1f,
+            hex!("d4976eb56bcf118520582b709f73e1d6853e001fdaf80e1b13e0d0599d5fb372"),
+        ),
+        (
+            vec![0xff],
+            0x01,
+            hex!("012ad664922ce3f81b058735b50aacbde383f1a9a75180b4b9f929550a5552b5"),
+        ),
+        (
+            vec![0xff; 3],
+            0x06,
+            hex!("3d03988bb59e681851a192f429ae03988e8f444bc06036a3f1a7d2ccd758d174"),
+        ),
+        (
+            vec![0xff; 7],
+            0x0b,
+            hex!("8deeaa1aec47ccee569f659c21dfa8e112db3cee37b18178b2acd805b799cc37"),
+        ),
+        (
+            vec![0xff; 7],
+            0x7f,
+            hex!("5834288ab65ff73eed3546a37f87c9195eae81d195f394451101a06bd226f8e0"),
+        ),
+    ];
+
+    for (msg, domain, digest) in samples {
+        let mut output = [0; 32];
+        turbo_shake128(msg.as_slice(), domain)
+            .unwrap()
+            .squeeze(&mut output);
+        assert_eq!(output, digest, "domain {domain:#04x}, {} bytes", msg.len());
+    }
+}
+
+#[test]
+fn turbo_shake128_long_output() {
+    let digest = hex!("a3b9b0385900ce761f22aed548e754da10a5242d62e8c658e3f3a923a7555607");
+
+    let mut output = vec![0; 10032];
+    turbo_shake128(std::io::empty(), 0x1f)
+        .unwrap()
+        .squeeze(&mut output);
+
+    assert_eq!(output[10000..], digest);
+}
+
+#[test]
+fn turbo_shake256_vectors() {
+    let samples: [(Vec<u8>, u8, [u8; 64]); 3] = [
+        (
+            vec![],
+            0x1f,
+            hex!("367a329dafea871c7802ec67f905ae13c57695dc2c6663c61035f59a18f8e7db11edc0e12e91ea60eb6b32df06dd7f002fbafabb6e13ec1cc20d995547600db0"),
+        ),
+        (
+            ptn(17),
+            0x1f,
+            hex!("b3bab0300e6a191fbe6137939835923578794ea54843f5011090fa2f3780a9e5cb22c59d78b40a0fbff9e672c0fbe0970bd2c845091c6044d687054da5d8e9c7"),
+        ),
+        (
+            vec![0xff; 3],
+            0x06,
+            hex!("e5538cdd28302a2e81e41f65fd2a4052014d0cd463df671d1e510a9d95c37d7135ef2728430a9e317004f836c9a238ef35370280d03dce7f0612f0315b3cbf63"),
+        ),
+    ];
+
+    for (msg, domain, digest) in samples {
+        let mut output = [0; 64];
+        turbo_shake256(msg.as_slice(), domain)
+            .unwrap()
+            .squeeze(&mut output);
+        assert_eq!(output, digest, "domain {domain:#04x}, {} bytes", msg.len());
+    }
+}
+
+#[test]
+#[should_panic]
+fn invalid_domain() {
+    let _ = turbo_shake128(std::io::empty(), 0x80);
+}